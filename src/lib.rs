@@ -1,13 +1,29 @@
-#![doc(html_root_url = "https://docs.rs/egui-resources/0.4.0")]
+#![doc(html_root_url = "https://docs.rs/egui-resources/0.5.0")]
 //! egui resources
 //!
 //! https://github.com/google/fonts/blob/main/ofl/firasans/FiraSans-Regular.ttf
 //!
+//! features
+//! - svg: resource_svg (resvg)
+//! - zip: ZipResources (zip)
+//! - datauri: color_image_from_data_uri (base64)
+//! - web: fetch_bytes fetch_img (ehttp on wasm32, ureq on native)
+//! - rayon: load_img_batch_par (rayon)
+//! - manifest: load_manifest (serde serde_json)
+//! - rust-embed: EmbeddedResources (rust-embed)
+//! - exif: resource_img_oriented (kamadak-exif)
+//! - webp: .webp for resource_img (image/webp)
+//! - avif: .avif for resource_img (image/avif-decoder, needs system libdav1d)
+//!
 
 use std::error::Error;
-use std::{fs, path::PathBuf};
-use std::io::Read;
+use std::collections::HashMap;
+use std::{fs, path::{Path, PathBuf}};
+use std::io::{Read, Seek, Cursor, BufReader};
+use std::time::{Duration, SystemTime};
 use image::{load_from_memory, DynamicImage, RgbaImage};
+use image::{AnimationDecoder, ImageFormat};
+use image::codecs::{gif::GifDecoder, png::PngDecoder};
 use image::imageops::FilterType;
 use eframe::{self, egui::*};
 
@@ -16,9 +32,10 @@ use eframe::{self, egui::*};
 /// - result: DynamicImage
 pub fn dynamic_image_from(src: &ColorImage) -> DynamicImage {
   let (sw, sh) = (src.width(), src.height());
-unsafe {
-  let p = &src.pixels[0] as *const Color32 as *const u8; // pointer
-  let s = std::slice::from_raw_parts(p, sw * sh * 4); // 4 for Color32
+  if src.pixels.is_empty() {
+    return DynamicImage::from(RgbaImage::new(sw as u32, sh as u32));
+  }
+  let s: &[u8] = bytemuck::cast_slice(&src.pixels); // Color32 is Pod
   DynamicImage::from(
     // RgbaImage is an alias of ImageBuffer
     match RgbaImage::from_raw(sw as u32, sh as u32, s.to_vec()) {
@@ -27,22 +44,425 @@ unsafe {
     }
   )
 }
+
+/// create DynamicImage from ColorImage without copying the pixel buffer
+/// - src: ColorImage (move)
+/// - result: DynamicImage
+///
+/// Color32 is Pod [u8; 4] (size 4 align 1),
+/// so Vec&lt;Color32&gt; is reinterpreted in place as Vec&lt;u8&gt; by bytemuck
+pub fn dynamic_image_from_owned(src: ColorImage) -> DynamicImage {
+  let (sw, sh) = (src.width(), src.height());
+  let v: Vec<u8> = bytemuck::allocation::cast_vec(src.pixels);
+  DynamicImage::from(
+    match RgbaImage::from_raw(sw as u32, sh as u32, v) {
+    None => RgbaImage::new(sw as u32, sh as u32),
+    Some(b) => b
+    }
+  )
 }
 
-/// create resized copy from ColorImage
+/// create DynamicImage from ColorImage with the smallest sufficient type
+/// - src: &amp;ColorImage
+/// - result: DynamicImage (ImageLuma8: gray and opaque, ImageRgb8: opaque,
+///   ImageRgba8: otherwise)
+pub fn dynamic_image_compact(src: &ColorImage) -> DynamicImage {
+  let (w, h) = (src.width() as u32, src.height() as u32);
+  if src.pixels.iter().any(|c| c.a() != 255) || validate(src).is_err() {
+    return dynamic_image_from(src);
+  }
+  if src.pixels.iter().all(|c| c.r() == c.g() && c.g() == c.b()) {
+    let v = src.pixels.iter().map(|c| c.r()).collect::<Vec<_>>();
+    if let Some(b) = image::GrayImage::from_raw(w, h, v) {
+      return DynamicImage::ImageLuma8(b);
+    }
+  }
+  let v = src.pixels.iter().flat_map(|c| [c.r(), c.g(), c.b()])
+    .collect::<Vec<_>>();
+  match image::RgbImage::from_raw(w, h, v) {
+  None => dynamic_image_from(src),
+  Some(b) => DynamicImage::ImageRgb8(b)
+  }
+}
+
+/// create DynamicImage with straight alpha from ColorImage (for encoders)
+/// - src: &amp;ColorImage
+/// - result: DynamicImage (ImageRgba8 of Color32::to_srgba_unmultiplied,
+///   the inverse of color_image_from_dynamic_image)
+fn straight_image_from(src: &ColorImage) -> DynamicImage {
+  let (sw, sh) = (src.width() as u32, src.height() as u32);
+  let v = src.pixels.iter().flat_map(|c| c.to_srgba_unmultiplied())
+    .collect::<Vec<_>>();
+  DynamicImage::from(match RgbaImage::from_raw(sw, sh, v) {
+  None => RgbaImage::new(sw, sh),
+  Some(b) => b
+  })
+}
+
+/// borrow tightly packed RGBA bytes [r, g, b, a, ...] of ColorImage
+/// - src: &amp;ColorImage
+/// - result: &amp;[u8] (premultiplied alpha, as stored in Color32)
+pub fn rgba_bytes_ref(src: &ColorImage) -> &[u8] {
+  bytemuck::cast_slice(&src.pixels) // Color32 is Pod
+}
+
+/// copy tightly packed RGBA bytes [r, g, b, a, ...] from ColorImage
+/// - src: &amp;ColorImage
+/// - result: Vec&lt;u8&gt; (premultiplied alpha, as stored in Color32)
+pub fn rgba_bytes(src: &ColorImage) -> Vec<u8> {
+  rgba_bytes_ref(src).to_vec()
+}
+
+/// save ColorImage
+/// - src: &amp;ColorImage
+/// - path: &amp;Path
+/// - format: image::ImageFormat (Png, Jpeg drops alpha, etc)
+/// - result: Result () (written with straight alpha, as files expect)
+pub fn save_color_image(src: &ColorImage, path: &Path, format: ImageFormat) ->
+  Result<(), Box<dyn Error>> {
+  let img = straight_image_from(src);
+  let img = match format {
+  ImageFormat::Jpeg => DynamicImage::from(img.to_rgb8()), // without alpha
+  _ => img
+  };
+  img.save_with_format(path, format)?;
+  Ok(())
+}
+
+/// save ColorImage as indexed png (palette of at most max_colors)
+/// - src: &amp;ColorImage
+/// - path: &amp;Path
+/// - max_colors: u16 (clamped to 1..=256, exact palette when src fits)
+/// - result: Result () (palette and tRNS hold straight alpha)
+pub fn save_indexed_png(src: &ColorImage, path: &Path, max_colors: u16) ->
+  Result<(), Box<dyn Error>> {
+  let n = max_colors.clamp(1, 256) as usize;
+  let rgba = straight_image_from(src).into_rgba8().into_raw();
+  let mut palette: Vec<[u8; 4]> = vec![];
+  let mut lut = HashMap::<[u8; 4], u8>::new();
+  for c in rgba.chunks_exact(4) {
+    let c = [c[0], c[1], c[2], c[3]];
+    if lut.contains_key(&c) { continue; }
+    if palette.len() == n { palette.clear(); break; } // too many colors
+    lut.insert(c, palette.len() as u8);
+    palette.push(c);
+  }
+  let indices: Vec<u8> = match palette.is_empty() {
+  false => rgba.chunks_exact(4).map(|c| lut[&[c[0], c[1], c[2], c[3]]])
+    .collect(),
+  true => {
+    let nq = color_quant::NeuQuant::new(10, n, &rgba);
+    palette = nq.color_map_rgba().chunks_exact(4)
+      .map(|c| [c[0], c[1], c[2], c[3]]).collect();
+    rgba.chunks_exact(4).map(|c| nq.index_of(c) as u8).collect()
+  }
+  };
+  let w = std::io::BufWriter::new(fs::File::create(path)?);
+  let mut enc = png::Encoder::new(w, src.width() as u32, src.height() as u32);
+  enc.set_color(png::ColorType::Indexed);
+  enc.set_depth(png::BitDepth::Eight);
+  enc.set_palette(palette.iter().flat_map(|c| [c[0], c[1], c[2]])
+    .collect::<Vec<_>>());
+  if palette.iter().any(|c| c[3] != 255) {
+    enc.set_trns(palette.iter().map(|c| c[3]).collect::<Vec<_>>());
+  }
+  enc.write_header()?.write_image_data(&indices)?;
+  Ok(())
+}
+
+/// FilterType from name
+/// - s: &amp;str (nearest triangle catmullrom gaussian lanczos3, ignore case)
+/// - result: Option FilterType (None for unknown name)
+pub fn filter_from_str(s: &str) -> Option<FilterType> {
+  match s.to_lowercase().as_str() {
+  "nearest" => Some(FilterType::Nearest),
+  "triangle" => Some(FilterType::Triangle),
+  "catmullrom" => Some(FilterType::CatmullRom),
+  "gaussian" => Some(FilterType::Gaussian),
+  "lanczos3" => Some(FilterType::Lanczos3),
+  _ => None
+  }
+}
+
+/// validate dimensions before resizing (shared by the resize entry points)
+/// - wh: [usize; 2]
+/// - result: Result (u32, u32) (Invalid when zero, beyond u32,
+///   or w * h * 4 bytes overflows usize)
+fn validate_dims(wh: [usize; 2]) -> Result<(u32, u32), ResourceError> {
+  let [w, h] = wh;
+  let err = || ResourceError::Invalid(format!("dimensions {}x{}", w, h));
+  if w == 0 || h == 0 { return Err(err()); }
+  let (Ok(w32), Ok(h32)) = (u32::try_from(w), u32::try_from(h)) else {
+    return Err(err());
+  };
+  w.checked_mul(h).and_then(|n| n.checked_mul(4)).ok_or_else(err)?;
+  Ok((w32, h32))
+}
+
+/// try create resized copy from ColorImage
 /// - wh: [usize; 2] (to be resized)
 /// - src: &amp;ColorImage
 /// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
-/// - result: ColorImage
-pub fn resized_copy_from(wh: [usize; 2], src: &ColorImage,
-  filter: FilterType) -> ColorImage {
-  let img = dynamic_image_from(src)
-    .resize_to_fill(wh[0] as u32, wh[1] as u32, filter);
+/// - result: Result ColorImage (Invalid when wh is zero or too large)
+pub fn try_resized_copy_from(wh: [usize; 2], src: &ColorImage,
+  filter: FilterType) -> Result<ColorImage, ResourceError> {
+  let (w, h) = validate_dims(wh)?;
+  let img = dynamic_image_from(src).resize_to_fill(w, h, filter);
   // always should use resize_to_fill for any aspect
-  color_image_from_dynamic_image(img)
+  Ok(color_image_from_dynamic_image(img))
   // ColorImage::from_rgba_unmultiplied(wh, &img.into_rgba8().into_raw())
 }
 
+/// create resized copy from ColorImage
+/// - wh: [usize; 2] (to be resized, 0 is clamped to 1)
+/// - src: &amp;ColorImage
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: ColorImage (ColorImage::example() when wh is too large)
+pub fn resized_copy_from(wh: [usize; 2], src: &ColorImage,
+  filter: FilterType) -> ColorImage {
+  try_resized_copy_from([wh[0].max(1), wh[1].max(1)], src, filter)
+    .unwrap_or_else(|_| ColorImage::example())
+}
+
+/// try create resized copy from ColorImage keeping aspect (letterbox)
+/// - wh: [usize; 2] (to be resized)
+/// - src: &amp;ColorImage
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - pad: Color32 (fill the area not covered by src)
+/// - result: Result ColorImage (Invalid when wh is zero or too large)
+pub fn try_resized_contain_from(wh: [usize; 2], src: &ColorImage,
+  filter: FilterType, pad: Color32) -> Result<ColorImage, ResourceError> {
+  validate_dims(wh)?;
+  let mut dst = ColorImage::new(wh, pad);
+  let (sw, sh) = (src.width(), src.height());
+  if sw == 0 || sh == 0 { return Ok(dst); }
+  let r = (wh[0] as f64 / sw as f64).min(wh[1] as f64 / sh as f64);
+  let dw = ((sw as f64 * r).round() as usize).clamp(1, wh[0]);
+  let dh = ((sh as f64 * r).round() as usize).clamp(1, wh[1]);
+  let img = color_image_from_dynamic_image_with(dynamic_image_from(src)
+    .resize_exact(dw as u32, dh as u32, filter), AlphaMode::Premultiplied);
+  let (ox, oy) = ((wh[0] - dw) / 2, (wh[1] - dh) / 2);
+  for y in 0..dh {
+    let d = (oy + y) * wh[0] + ox;
+    dst.pixels[d..d + dw].copy_from_slice(&img.pixels[y * dw..(y + 1) * dw]);
+  }
+  Ok(dst)
+}
+
+/// create resized copy from ColorImage keeping aspect (letterbox)
+/// - wh: [usize; 2] (to be resized)
+/// - src: &amp;ColorImage
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - pad: Color32 (fill the area not covered by src)
+/// - result: ColorImage (empty when wh has 0,
+///   ColorImage::example() when wh is too large)
+pub fn resized_contain_from(wh: [usize; 2], src: &ColorImage,
+  filter: FilterType, pad: Color32) -> ColorImage {
+  if wh[0] == 0 || wh[1] == 0 { return ColorImage::new(wh, pad); }
+  try_resized_contain_from(wh, src, filter, pad)
+    .unwrap_or_else(|_| ColorImage::example())
+}
+
+/// try create thumbnail from ColorImage (longer edge is capped)
+/// - src: &amp;ColorImage
+/// - max_edge: usize
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: Result ColorImage (copy of src when both edges are within
+///   max_edge, Invalid when max_edge is 0 or too large)
+pub fn try_thumbnail_from(src: &ColorImage, max_edge: usize,
+  filter: FilterType) -> Result<ColorImage, ResourceError> {
+  let (m, _) = validate_dims([max_edge, 1])?;
+  if src.width().max(src.height()) <= max_edge { return Ok(src.clone()); }
+  let img = dynamic_image_from(src).resize(m, m, filter);
+  Ok(color_image_from_dynamic_image(img))
+}
+
+/// create thumbnail from ColorImage (longer edge is capped, never upscaled)
+/// - src: &amp;ColorImage
+/// - max_edge: usize
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: ColorImage (copy of src when both edges are within max_edge,
+///   ColorImage::example() when max_edge is 0)
+pub fn thumbnail_from(src: &ColorImage, max_edge: usize,
+  filter: FilterType) -> ColorImage {
+  if src.width().max(src.height()) <= max_edge { return src.clone(); }
+  try_thumbnail_from(src, max_edge, filter)
+    .unwrap_or_else(|_| ColorImage::example())
+}
+
+/// try create 9-slice scaled copy from ColorImage (nine-patch)
+/// - src: &amp;ColorImage
+/// - border: [usize; 4] (insets top right bottom left, clamped into both)
+/// - target: [usize; 2] (of the result)
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: Result ColorImage (Invalid when target is zero or too large)
+pub fn try_nine_slice(src: &ColorImage, border: [usize; 4],
+  target: [usize; 2], filter: FilterType) -> Result<ColorImage, ResourceError> {
+  validate_dims(target)?;
+  // segments [0, a) [a, n - b) [n - b, n) of src n and target m on an axis
+  let segs = |n: usize, m: usize, a: usize, b: usize| {
+    let a = a.min(n).min(m);
+    let b = b.min(n - a).min(m - a);
+    ([(0, a), (a, n - b), (n - b, n)], [(0, a), (a, m - b), (m - b, m)])
+  };
+  let (xs, xd) = segs(src.width(), target[0], border[3], border[1]);
+  let (ys, yd) = segs(src.height(), target[1], border[0], border[2]);
+  let mut dst = ColorImage::new(target, Color32::TRANSPARENT);
+  for (sy, dy) in ys.iter().zip(yd.iter()) {
+    for (sx, dx) in xs.iter().zip(xd.iter()) {
+      let (sw, sh) = (sx.1 - sx.0, sy.1 - sy.0);
+      let (dw, dh) = (dx.1 - dx.0, dy.1 - dy.0);
+      if sw == 0 || sh == 0 || dw == 0 || dh == 0 { continue; }
+      let mut cell = crop_color_image(src, sx.0, sy.0, sw, sh);
+      if [sw, sh] != [dw, dh] {
+        let img = dynamic_image_from(&cell)
+          .resize_exact(dw as u32, dh as u32, filter);
+        cell = color_image_from_dynamic_image_with(img,
+          AlphaMode::Premultiplied);
+      }
+      for y in 0..dh {
+        let d = (dy.0 + y) * target[0] + dx.0;
+        let r = &cell.pixels[y * dw..(y + 1) * dw];
+        dst.pixels[d..d + dw].copy_from_slice(r);
+      }
+    }
+  }
+  Ok(dst)
+}
+
+/// create 9-slice scaled copy from ColorImage (nine-patch for UI panels)
+/// - src: &amp;ColorImage
+/// - border: [usize; 4] (insets top right bottom left, clamped into both)
+/// - target: [usize; 2] (of the result)
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: ColorImage (corners are copied, edges and center are stretched,
+///   empty when target has 0, ColorImage::example() when too large)
+pub fn nine_slice(src: &ColorImage, border: [usize; 4], target: [usize; 2],
+  filter: FilterType) -> ColorImage {
+  if target[0] == 0 || target[1] == 0 {
+    return ColorImage::new(target, Color32::TRANSPARENT);
+  }
+  try_nine_slice(src, border, target, filter)
+    .unwrap_or_else(|_| ColorImage::example())
+}
+
+/// try create resized copy from ColorImage with extruded edges (for atlas)
+/// - src: &amp;ColorImage
+/// - wh: [usize; 2] (to be resized)
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - extrude: usize (border pixels are repeated outward by extrude)
+/// - result: Result ColorImage (wh + extrude * 2 on each axis,
+///   Invalid when wh is zero or the result is too large)
+pub fn try_resize_with_edge_extend(src: &ColorImage, wh: [usize; 2],
+  filter: FilterType, extrude: usize) -> Result<ColorImage, ResourceError> {
+  let [w, h] = wh;
+  let e = extrude;
+  let ext = |v: usize| v.saturating_add(e.saturating_mul(2));
+  validate_dims([ext(w), ext(h)])?;
+  let img = try_resized_premultiplied(src, wh, filter)?;
+  Ok(remapped_from([w + e * 2, h + e * 2], &img, |x, y|
+    y.saturating_sub(e).min(h - 1) * w + x.saturating_sub(e).min(w - 1)))
+}
+
+/// create resized copy from ColorImage with extruded edges (for atlas)
+/// - src: &amp;ColorImage
+/// - wh: [usize; 2] (to be resized, 0 is clamped to 1)
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - extrude: usize (border pixels are repeated outward by extrude)
+/// - result: ColorImage (wh + extrude * 2 on each axis,
+///   ColorImage::example() when too large)
+pub fn resize_with_edge_extend(src: &ColorImage, wh: [usize; 2],
+  filter: FilterType, extrude: usize) -> ColorImage {
+  try_resize_with_edge_extend(src, [wh[0].max(1), wh[1].max(1)], filter,
+    extrude).unwrap_or_else(|_| ColorImage::example())
+}
+
+/// try create resized copy from ColorImage weighting rgb by alpha (no halo)
+/// - src: &amp;ColorImage
+/// - wh: [usize; 2] (to be resized)
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: Result ColorImage (Invalid when wh is zero or too large)
+///
+/// ColorImage pixels are premultiplied already, so they are resampled as is
+/// and kept premultiplied (transparent rgb does not bleed into the edges)
+pub fn try_resized_premultiplied(src: &ColorImage, wh: [usize; 2],
+  filter: FilterType) -> Result<ColorImage, ResourceError> {
+  let (w, h) = validate_dims(wh)?;
+  let img = dynamic_image_from(src).resize_to_fill(w, h, filter);
+  Ok(color_image_from_dynamic_image_with(img, AlphaMode::Premultiplied))
+}
+
+/// create resized copy from ColorImage weighting rgb by alpha (no halo)
+/// - src: &amp;ColorImage
+/// - wh: [usize; 2] (to be resized, 0 is clamped to 1)
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: ColorImage (ColorImage::example() when wh is too large)
+pub fn resized_premultiplied(src: &ColorImage, wh: [usize; 2],
+  filter: FilterType) -> ColorImage {
+  try_resized_premultiplied(src, [wh[0].max(1), wh[1].max(1)], filter)
+    .unwrap_or_else(|_| ColorImage::example())
+}
+
+/// create resized copy from ColorImage for the physical pixels (HiDPI)
+/// - src: &amp;ColorImage
+/// - logical_wh: [usize; 2] (size in points)
+/// - pixels_per_point: f32 (ctx.pixels_per_point(), 1.25 1.5 2.0 etc)
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: ColorImage (logical_wh * pixels_per_point rounded)
+pub fn resized_for_dpi(src: &ColorImage, logical_wh: [usize; 2],
+  pixels_per_point: f32, filter: FilterType) -> ColorImage {
+  let s = |v: usize| (v as f32 * pixels_per_point).round() as usize;
+  resized_premultiplied(src, [s(logical_wh[0]), s(logical_wh[1])], filter)
+}
+
+/// generate mipmap chain from ColorImage
+/// - src: &amp;ColorImage
+/// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+/// - result: Vec&lt;ColorImage&gt; (src then halved each level down to 1x1,
+///   resampled premultiplied)
+pub fn generate_mipmaps(src: &ColorImage, filter: FilterType) ->
+  Vec<ColorImage> {
+  let mut mips = vec![src.clone()];
+  loop {
+    let [w, h] = mips[mips.len() - 1].size;
+    if w <= 1 && h <= 1 { break; }
+    let wh = [w.div_ceil(2).max(1), h.div_ceil(2).max(1)];
+    let mip = resized_premultiplied(&mips[mips.len() - 1], wh, filter);
+    mips.push(mip);
+  }
+  mips
+}
+
+/// create copy from ColorImage processed by f keeping the alpha of src
+/// - src: &amp;ColorImage
+/// - f: FnOnce(DynamicImage) -&gt; DynamicImage (same size, straight alpha)
+/// - result: ColorImage (unmultiplied before f, premultiplied again after,
+///   both the same way as Color32)
+fn filtered_keep_alpha(src: &ColorImage,
+  f: impl FnOnce(DynamicImage) -> DynamicImage) -> ColorImage {
+  let mut img = f(straight_image_from(src)).into_rgba8();
+  for (d, s) in img.pixels_mut().zip(src.pixels.iter()) { d.0[3] = s.a(); }
+  color_image_from_dynamic_image(DynamicImage::from(img))
+}
+
+/// create blurred copy from ColorImage (gaussian, alpha is kept)
+/// - src: &amp;ColorImage
+/// - sigma: f32 (larger is significantly more expensive)
+/// - result: ColorImage
+pub fn blur_color_image(src: &ColorImage, sigma: f32) -> ColorImage {
+  filtered_keep_alpha(src, |img| img.blur(sigma))
+}
+
+/// create sharpened copy from ColorImage (unsharpen mask, alpha is kept)
+/// - src: &amp;ColorImage
+/// - sigma: f32 (larger is significantly more expensive)
+/// - threshold: i32 (minimum difference to be sharpened)
+/// - result: ColorImage
+pub fn sharpen_color_image(src: &ColorImage, sigma: f32,
+  threshold: i32) -> ColorImage {
+  filtered_keep_alpha(src, |img| img.unsharpen(sigma, threshold))
+}
+
 /// macro im_flat
 /// - img: image::DynamicImage
 /// - result: ([u8], u32, u32)
@@ -57,132 +477,4039 @@ macro_rules! im_flat {
 }
 // pub use im_flat;
 
+/// AlphaMode (how rgb of the source relates to alpha)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlphaMode {
+  /// rgb is not multiplied by alpha (usual png files)
+  Straight,
+  /// rgb is already multiplied by alpha (rendered glyphs, composited sprites)
+  Premultiplied
+}
+
+/// create ColorImage from DynamicImage with AlphaMode
+/// - src: DynamicImage (move)
+/// - alpha: AlphaMode
+/// - result: ColorImage
+pub fn color_image_from_dynamic_image_with(src: DynamicImage,
+  alpha: AlphaMode) -> ColorImage {
+  let (rgba, width, height) = im_flat!(src);
+  let size = [width as usize, height as usize];
+  match alpha {
+  AlphaMode::Straight => ColorImage::from_rgba_unmultiplied(size, &rgba),
+  AlphaMode::Premultiplied => {
+    let pixels = rgba.chunks_exact(4).map(|c|
+      Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3])
+    ).collect::<Vec<_>>();
+    ColorImage{size, pixels}
+  }
+  }
+}
+
 /// create ColorImage from DynamicImage
 /// - src: DynamicImage (move)
 /// - result: ColorImage
 pub fn color_image_from_dynamic_image(src: DynamicImage) -> ColorImage {
-  let (rgba, width, height) = im_flat!(src);
-  ColorImage::from_rgba_unmultiplied(
-    [width as usize, height as usize], &rgba)
+  color_image_from_dynamic_image_with(src, AlphaMode::Straight)
 }
 
-/// ResourceBase
-pub struct ResourcesBase {
-  /// base path
-  pub basepath: PathBuf
+/// create ColorImage from DynamicImage (already premultiplied alpha)
+/// - src: DynamicImage (move)
+/// - result: ColorImage
+///
+/// use this when rgb of src is already multiplied by alpha
+/// (rendered glyphs, composited sprites),
+/// use color_image_from_dynamic_image for straight alpha (usual png files)
+pub fn color_image_from_dynamic_image_premultiplied(src: DynamicImage) ->
+  ColorImage {
+  color_image_from_dynamic_image_with(src, AlphaMode::Premultiplied)
 }
 
-/// ResourcesBase
-impl ResourcesBase {
-  /// constructor
-  /// - basepath: PathBuf base path (move)
-  pub fn new(basepath: PathBuf) -> Self {
-    ResourcesBase{basepath}
-  }
+/// create solid color ColorImage
+/// - wh: [usize; 2] (zero makes an empty image)
+/// - color: Color32
+/// - result: ColorImage
+pub fn solid_color_image(wh: [usize; 2], color: Color32) -> ColorImage {
+  ColorImage{size: wh, pixels: vec![color; wh[0] * wh[1]]}
+}
 
-  /// load resource img
-  /// - f: &amp;str filename
-  /// - p: bool (true: self.basepath false: full path)
-  /// - result: ColorImage
-  pub fn resource_img(&self, f: &str, p: bool) -> ColorImage {
-    let Ok(b) = self.read_bytes(f, p) else { return ColorImage::example(); };
-    if let Ok(img) = load_from_memory(&b) {
-      color_image_from_dynamic_image(img)
-    }else{
-      ColorImage::example()
-    }
-  }
+/// create checkerboard placeholder (missing texture, for resource_img_or)
+/// - wh: [usize; 2]
+/// - a: Color32 (the top left cell)
+/// - b: Color32
+/// - cell: usize (edge of the cell in pixels, 0 is clamped to 1)
+/// - result: ColorImage
+pub fn placeholder_checkerboard(wh: [usize; 2], a: Color32, b: Color32,
+  cell: usize) -> ColorImage {
+  let cell = cell.max(1);
+  let pixels = (0..wh[0] * wh[1]).map(|i| {
+    let (x, y) = (i % wh[0], i / wh[0]);
+    if (x / cell + y / cell).is_multiple_of(2) { a } else { b }
+  }).collect::<Vec<_>>();
+  ColorImage{size: wh, pixels}
+}
 
-  /// load resource icon
-  /// - ico: &amp;str filename
-  /// - p: bool (true: self.basepath false: full path)
-  /// - result: Option eframe::IconData
-  pub fn resource_icon(&self, ico: &str, p: bool) -> Option<eframe::IconData> {
-    let Ok(b) = self.read_bytes(ico, p) else { return None; };
-    if let Ok(img) = load_from_memory(&b) {
-      let (rgba, width, height) = im_flat!(img);
-      Some(eframe::IconData{rgba, width, height})
-    }else{
-      None
+/// create premultiplied copy from ColorImage holding straight alpha bytes
+/// - src: &amp;ColorImage (rgb of each pixel is not multiplied by alpha yet)
+/// - result: ColorImage (rgb = rgb * a / 255, rounded)
+///
+/// sRGB-space math for an external renderer, not the Color32 encoding
+/// (egui premultiplies in linear space, use Color32::from_rgba_unmultiplied)
+pub fn premultiply(src: &ColorImage) -> ColorImage {
+  let pixels = src.pixels.iter().map(|c| {
+    let (r, g, b, a) = c.to_tuple();
+    let m = |v: u8| ((v as u16 * a as u16 + 127) / 255) as u8;
+    Color32::from_rgba_premultiplied(m(r), m(g), m(b), a)
+  }).collect::<Vec<_>>();
+  ColorImage{size: src.size, pixels}
+}
+
+/// create straight alpha copy from premultiplied ColorImage
+/// - src: &amp;ColorImage
+/// - result: ColorImage (rgb = rgb * 255 / a, rounded, 0 when a is 0)
+///
+/// sRGB-space math for an external renderer, the inverse of premultiply,
+/// not of Color32 (use Color32::to_srgba_unmultiplied for egui pixels)
+pub fn unpremultiply(src: &ColorImage) -> ColorImage {
+  let pixels = src.pixels.iter().map(|c| {
+    let (r, g, b, a) = c.to_tuple();
+    let d = |v: u8| match a {
+    0 => 0,
+    _ => ((v as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8
+    };
+    Color32::from_rgba_premultiplied(d(r), d(g), d(b), a)
+  }).collect::<Vec<_>>();
+  ColorImage{size: src.size, pixels}
+}
+
+/// create brightness and contrast adjusted copy from ColorImage
+/// - src: &amp;ColorImage
+/// - brightness: i32 (added to each rgb channel, clamped)
+/// - contrast: f32 (percent, positive increases, negative decreases)
+/// - result: ColorImage (alpha is kept)
+pub fn adjust(src: &ColorImage, brightness: i32, contrast: f32) ->
+  ColorImage {
+  filtered_keep_alpha(src, |img| img.brighten(brightness)
+    .adjust_contrast(contrast))
+}
+
+/// create gamma corrected copy from ColorImage
+/// - src: &amp;ColorImage
+/// - gamma: f32 (v = (v / 255) ^ (1 / gamma) * 255, &gt; 1 brightens)
+/// - result: ColorImage (alpha is kept, copy of src when gamma &lt;= 0)
+pub fn gamma_correct(src: &ColorImage, gamma: f32) -> ColorImage {
+  if gamma <= 0.0 { return src.clone(); }
+  let lut = (0..=255).map(|v|
+    (255.0 * (v as f32 / 255.0).powf(1.0 / gamma)).round() as u8
+  ).collect::<Vec<_>>();
+  filtered_keep_alpha(src, |img| {
+    let mut img = img.into_rgba8();
+    for px in img.pixels_mut() {
+      for v in px.0[..3].iter_mut() { *v = lut[*v as usize]; }
     }
-  }
+    DynamicImage::from(img)
+  })
+}
 
-  /// load resource font
-  /// - fonts: &amp;mut FontDefinitions
-  /// - n: &amp;str name
-  /// - f: &amp;str filename
-  /// - t: FontFamily family (move)
-  /// - p: bool (true: self.basepath false: full path)
-  /// - result: ()
-  pub fn resource_font(&self, fonts: &mut FontDefinitions,
-    n: &str, f: &str, t: FontFamily, p: bool) {
-    let Ok(b) = self.read_bytes(f, p) else { return; };
-    let n = n.to_string();
-    let m = n.clone();
-    fonts.font_data.insert(n, FontData::from_owned(b));
-    // fonts.font_data.insert(n, FontData::from_static(include_bytes!(
-    //   "static str path from src extended and read at the compile time")));
-    fonts.families.entry(t).or_default().insert(0, m);
+/// create copy from ColorImage with alpha scaled (for fade)
+/// - src: &amp;ColorImage
+/// - factor: f32 (clamped into 0..1)
+/// - result: ColorImage (straight rgb is kept, premultiplied again as Color32)
+pub fn set_opacity(src: &ColorImage, factor: f32) -> ColorImage {
+  let k = factor.clamp(0.0, 1.0);
+  let pixels = src.pixels.iter().map(|c| {
+    let [r, g, b, a] = c.to_srgba_unmultiplied();
+    Color32::from_rgba_unmultiplied(r, g, b, (a as f32 * k).round() as u8)
+  }).collect::<Vec<_>>();
+  ColorImage{size: src.size, pixels}
+}
+
+/// split ColorImage into channel planes
+/// - src: &amp;ColorImage
+/// - result: [Vec&lt;u8&gt;; 4] (r g b a planes, premultiplied values)
+pub fn split_channels(src: &ColorImage) -> [Vec<u8>; 4] {
+  let mut planes: [Vec<u8>; 4] = Default::default();
+  for p in planes.iter_mut() { p.reserve(src.pixels.len()); }
+  for c in src.pixels.iter() {
+    for (p, v) in planes.iter_mut().zip(c.to_array()) { p.push(v); }
   }
+  planes
+}
 
-  /// reg fonts
-  /// - ffs: Vec&lt; (name, filename, family) &gt; (move)
-  /// - result: FontDefinitions
-  pub fn reg_fonts(&self, ffs: Vec<(&str, &str, FontFamily)>) ->
-    FontDefinitions {
-    let mut fonts = FontDefinitions::default();
-    for (n, f, t) in ffs.into_iter() {
-      self.resource_font(&mut fonts, n, f, t, !f.contains("/"));
+/// merge channel planes into ColorImage
+/// - wh: [usize; 2]
+/// - r: &amp;[u8]
+/// - g: &amp;[u8]
+/// - b: &amp;[u8]
+/// - a: &amp;[u8]
+/// - result: ColorImage (premultiplied values, short planes are filled by 0)
+pub fn merge_channels(wh: [usize; 2],
+  r: &[u8], g: &[u8], b: &[u8], a: &[u8]) -> ColorImage {
+  let v = |c: &[u8], i: usize| c.get(i).copied().unwrap_or(0);
+  let pixels = (0..wh[0] * wh[1]).map(|i|
+    Color32::from_rgba_premultiplied(v(r, i), v(g, i), v(b, i), v(a, i))
+  ).collect::<Vec<_>>();
+  ColorImage{size: wh, pixels}
+}
+
+/// create color keyed copy from ColorImage (key color becomes transparent)
+/// - src: &amp;ColorImage
+/// - key: Color32 (rgb is compared, alpha is ignored)
+/// - tolerance: u8 (max diff allowed for every rgb channel)
+/// - result: ColorImage (others are untouched)
+pub fn color_key(src: &ColorImage, key: Color32, tolerance: u8) -> ColorImage {
+  let k = key.to_array();
+  let pixels = src.pixels.iter().map(|c| {
+    let v = c.to_array();
+    match (0..3).all(|i| v[i].abs_diff(k[i]) <= tolerance) {
+    true => Color32::TRANSPARENT,
+    false => *c
     }
-    fonts
-  }
+  }).collect::<Vec<_>>();
+  ColorImage{size: src.size, pixels}
+}
 
-  /// read bytes
-  /// - f: &amp;str filename
-  /// - p: bool (true: self.basepath false: full path)
-  /// - result: Result Vec u8
-  pub fn read_bytes(&self, f: &str, p: bool) ->
-    Result<Vec<u8>, Box<dyn Error>> {
-    let p = if !p { PathBuf::from(f) } else { self.basepath.join(f) };
-    let mut fi = fs::File::open(&p)?;
-    let metadata = fs::metadata(&p)?;
-    let mut buf = vec![0u8; metadata.len() as usize];
-    fi.read(&mut buf)?;
-    Ok(buf)
+/// create dithered copy from ColorImage (Floyd-Steinberg error diffusion)
+/// - src: &amp;ColorImage
+/// - levels: u8 (steps of each rgb channel, clamped into 2..=255)
+/// - result: ColorImage (alpha is kept)
+pub fn dither_color_image(src: &ColorImage, levels: u8) -> ColorImage {
+  let [w, h] = src.size;
+  let step = 255.0 / (levels.max(2) - 1) as f32;
+  let mut buf = src.pixels.iter().map(|c|
+    [c.r() as f32, c.g() as f32, c.b() as f32]).collect::<Vec<_>>();
+  let mut pixels = src.pixels.clone();
+  for y in 0..h {
+    for x in 0..w {
+      let i = y * w + x;
+      let mut q = [0u8; 3];
+      for ch in 0..3 {
+        let v = buf[i][ch];
+        let n = ((v / step).round() * step).clamp(0.0, 255.0);
+        q[ch] = n.round() as u8;
+        let e = v - n;
+        let mut spread = |dx: isize, dy: usize, k: f32| {
+          let nx = x as isize + dx;
+          if nx < 0 || nx >= w as isize || y + dy >= h { return; }
+          buf[(y + dy) * w + nx as usize][ch] += e * k;
+        };
+        spread(1, 0, 7.0 / 16.0);
+        spread(-1, 1, 3.0 / 16.0);
+        spread(0, 1, 5.0 / 16.0);
+        spread(1, 1, 1.0 / 16.0);
+      }
+      let a = src.pixels[i].a(); // premultiplied rgb must not exceed a
+      let (r, g, b) = (q[0].min(a), q[1].min(a), q[2].min(a));
+      pixels[i] = Color32::from_rgba_premultiplied(r, g, b, a);
+    }
   }
+  ColorImage{size: src.size, pixels}
 }
 
-/// tests
-#[cfg(test)]
-mod tests {
-  use super::*;
+/// create tinted copy from ColorImage (multiply each channel by tint)
+/// - src: &amp;ColorImage
+/// - tint: Color32 (opaque tint keeps the alpha of src)
+/// - result: ColorImage
+pub fn tint_color_image(src: &ColorImage, tint: Color32) -> ColorImage {
+  let t = tint.to_array();
+  let pixels = src.pixels.iter().map(|c| {
+    let c = c.to_array();
+    let m = |i: usize| ((c[i] as u16 * t[i] as u16 + 127) / 255) as u8;
+    Color32::from_rgba_premultiplied(m(0), m(1), m(2), m(3))
+  }).collect::<Vec<_>>();
+  ColorImage{size: src.size, pixels}
+}
 
-  /// [-- --nocapture] [-- --show-output]
-  #[test]
-  fn test_resources() {
-    let bp = ResourcesBase::new(PathBuf::from("./resources"));
-    let flat = vec![
-[255, 0, 0, 255], [255, 0, 0, 255], [0, 255, 0, 255], [0, 255, 0, 255],
-[255, 0, 0, 255], [255, 0, 0, 255], [0, 255, 0, 255], [0, 255, 0, 255],
-[0, 0, 255, 255], [0, 0, 255, 255], [255, 255, 0, 255], [255, 255, 0, 255],
-[0, 0, 255, 255], [0, 0, 255, 255], [255, 255, 0, 255], [255, 255, 0, 255]
-    ].into_iter().map(|c|
-      Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3]) // unmultiplied
-    ).collect::<Vec<_>>();
-    let im = bp.resource_img("_4c_4x4.png", true);
-    assert_eq!(im.size, [4, 4]);
-    assert_eq!(im.pixels.len(), 16);
-    assert_eq!(im.pixels, flat);
+/// create grayscale copy from ColorImage (luma 0.299 0.587 0.114)
+/// - src: &amp;ColorImage
+/// - result: ColorImage (alpha is kept)
+pub fn grayscale_color_image(src: &ColorImage) -> ColorImage {
+  let pixels = src.pixels.iter().map(|c| {
+    let (r, g, b, a) = c.to_tuple(); // premultiplied then luma &lt;= a
+    let l = (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32).round();
+    Color32::from_rgba_premultiplied(l as u8, l as u8, l as u8, a)
+  }).collect::<Vec<_>>();
+  ColorImage{size: src.size, pixels}
+}
 
-    let resized = vec![
-      [255, 0, 0, 255], [0, 255, 0, 255],
-      [0, 0, 255, 255], [255, 255, 0, 255]
-    ].into_iter().map(|c|
-      Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3]) // unmultiplied
-    ).collect::<Vec<_>>();
-    let img = resized_copy_from([2, 2], &im, FilterType::Nearest);
-    assert_eq!(img.size, [2, 2]);
-    assert_eq!(img.pixels.len(), 4);
-    assert_eq!(img.pixels, resized);
+/// create recolored copy from ColorImage (silhouette)
+/// - src: &amp;ColorImage
+/// - color: Color32 (rgb is used, alpha of src is kept)
+/// - result: ColorImage
+pub fn recolor_opaque(src: &ColorImage, color: Color32) -> ColorImage {
+  let (r, g, b, _) = color.to_tuple();
+  let pixels = src.pixels.iter().map(|c|
+    Color32::from_rgba_unmultiplied(r, g, b, c.a())
+  ).collect::<Vec<_>>();
+  ColorImage{size: src.size, pixels}
+}
+
+/// pixel at (x, y) of ColorImage (bounds checked against size)
+/// - img: &amp;ColorImage
+/// - x: usize
+/// - y: usize
+/// - result: Option Color32 (None when out of bounds)
+pub fn pixel_at(img: &ColorImage, x: usize, y: usize) -> Option<Color32> {
+  let [w, h] = img.size;
+  if x >= w || y >= h { return None; }
+  img.pixels.get(y * w + x).copied()
+}
+
+/// set pixel at (x, y) of ColorImage (bounds checked against size)
+/// - img: &amp;mut ColorImage
+/// - x: usize
+/// - y: usize
+/// - c: Color32
+/// - result: bool (false when out of bounds and nothing is changed)
+pub fn set_pixel(img: &mut ColorImage, x: usize, y: usize, c: Color32) ->
+  bool {
+  let [w, h] = img.size;
+  if x >= w || y >= h { return false; }
+  match img.pixels.get_mut(y * w + x) {
+  None => false,
+  Some(px) => { *px = c; true }
   }
-}
\ No newline at end of file
+}
+
+/// create remapped copy from ColorImage
+/// - size: [usize; 2] (of the result)
+/// - src: &amp;ColorImage
+/// - f: Fn(x, y) -&gt; index of src.pixels for the result pixel (x, y)
+/// - result: ColorImage
+fn remapped_from(size: [usize; 2], src: &ColorImage,
+  f: impl Fn(usize, usize) -> usize) -> ColorImage {
+  let pixels = (0..size[1]).flat_map(|y|
+    (0..size[0]).map(move |x| (x, y))
+  ).map(|(x, y)| src.pixels[f(x, y)]).collect::<Vec<_>>();
+  ColorImage{size, pixels}
+}
+
+/// create horizontally flipped copy from ColorImage
+/// - src: &amp;ColorImage
+/// - result: ColorImage
+pub fn flip_horizontal(src: &ColorImage) -> ColorImage {
+  let [w, h] = src.size;
+  remapped_from([w, h], src, |x, y| y * w + (w - 1 - x))
+}
+
+/// create vertically flipped copy from ColorImage
+/// - src: &amp;ColorImage
+/// - result: ColorImage
+pub fn flip_vertical(src: &ColorImage) -> ColorImage {
+  let [w, h] = src.size;
+  remapped_from([w, h], src, |x, y| (h - 1 - y) * w + x)
+}
+
+/// create 90 degrees clockwise rotated copy from ColorImage
+/// - src: &amp;ColorImage
+/// - result: ColorImage (width and height are swapped)
+pub fn rotate90(src: &ColorImage) -> ColorImage {
+  let [w, h] = src.size;
+  remapped_from([h, w], src, |x, y| (h - 1 - x) * w + y)
+}
+
+/// create 180 degrees rotated copy from ColorImage
+/// - src: &amp;ColorImage
+/// - result: ColorImage
+pub fn rotate180(src: &ColorImage) -> ColorImage {
+  let [w, h] = src.size;
+  remapped_from([w, h], src, |x, y| (h - 1 - y) * w + (w - 1 - x))
+}
+
+/// create 270 degrees clockwise rotated copy from ColorImage
+/// - src: &amp;ColorImage
+/// - result: ColorImage (width and height are swapped)
+pub fn rotate270(src: &ColorImage) -> ColorImage {
+  let [w, h] = src.size;
+  remapped_from([h, w], src, |x, y| x * w + (w - 1 - y))
+}
+
+/// create copy from ColorImage oriented by the EXIF orientation tag
+/// - src: &amp;ColorImage
+/// - orientation: u32 (1: as is, 2: flip h, 3: 180, 4: flip v,
+///   5: transpose, 6: 90 cw, 7: transverse, 8: 270 cw, others as 1)
+/// - result: ColorImage
+pub fn oriented_color_image(src: &ColorImage, orientation: u32) ->
+  ColorImage {
+  match orientation {
+  2 => flip_horizontal(src),
+  3 => rotate180(src),
+  4 => flip_vertical(src),
+  5 => flip_horizontal(&rotate90(src)),
+  6 => rotate90(src),
+  7 => flip_horizontal(&rotate270(src)),
+  8 => rotate270(src),
+  _ => src.clone()
+  }
+}
+
+/// create cropped copy from ColorImage
+/// - src: &amp;ColorImage
+/// - x: usize left
+/// - y: usize top
+/// - w: usize width (clamped into src)
+/// - h: usize height (clamped into src)
+/// - result: ColorImage (may be empty when x or y is out of src)
+pub fn crop_color_image(src: &ColorImage,
+  x: usize, y: usize, w: usize, h: usize) -> ColorImage {
+  let [sw, sh] = src.size;
+  let (x, y) = (x.min(sw), y.min(sh));
+  let size = [w.min(sw - x), h.min(sh - y)];
+  remapped_from(size, src, |i, j| (y + j) * sw + x + i)
+}
+
+/// create trimmed copy from ColorImage (autocrop transparent borders)
+/// - src: &amp;ColorImage
+/// - result: (ColorImage, [usize; 4] removed top right bottom left)
+///
+/// fully transparent src (and src failed to validate, as if it has no
+/// opaque pixels) returns 0x0 with [height, 0, 0, width]
+pub fn trim_transparent(src: &ColorImage) -> (ColorImage, [usize; 4]) {
+  let [w, h] = src.size;
+  if validate(src).is_err() {
+    return (ColorImage::new([0, 0], Color32::TRANSPARENT), [h, 0, 0, w]);
+  }
+  let row = |y: usize| (0..w).any(|x| src.pixels[y * w + x].a() > 0);
+  let col = |x: usize| (0..h).any(|y| src.pixels[y * w + x].a() > 0);
+  let rows = (0..h).map(row).collect::<Vec<_>>();
+  let Some(top) = rows.iter().position(|&r| r) else {
+    return (ColorImage::new([0, 0], Color32::TRANSPARENT), [h, 0, 0, w]);
+  };
+  let bottom = rows.iter().rposition(|&r| r).unwrap_or(top);
+  let cols = (0..w).map(col).collect::<Vec<_>>();
+  let left = cols.iter().position(|&c| c).unwrap_or(0);
+  let right = cols.iter().rposition(|&c| c).unwrap_or(left);
+  let (cw, ch) = (right + 1 - left, bottom + 1 - top);
+  let img = crop_color_image(src, left, top, cw, ch);
+  (img, [top, w - 1 - right, h - 1 - bottom, left])
+}
+
+/// create center cropped copy from ColorImage matching aspect (no resample)
+/// - src: &amp;ColorImage
+/// - num: usize (width ratio)
+/// - den: usize (height ratio)
+/// - result: ColorImage (copy of src when num or den is 0)
+pub fn crop_to_aspect(src: &ColorImage, num: usize, den: usize) -> ColorImage {
+  let [w, h] = src.size;
+  if num == 0 || den == 0 { return src.clone(); }
+  let (w, h, n, d) = (w as u128, h as u128, num as u128, den as u128);
+  // u128 does not overflow, the cropped edge is within the src edge
+  let (cw, ch) = if w * d >= h * n { (h * n / d, h) } else { (w, w * d / n) };
+  let (w, h, cw, ch) = (w as usize, h as usize, cw as usize, ch as usize);
+  crop_color_image(src, (w - cw) / 2, (h - ch) / 2, cw, ch)
+}
+
+/// slice atlas into tiles
+/// - src: &amp;ColorImage
+/// - cols: usize (must divide src width)
+/// - rows: usize (must divide src height)
+/// - result: Result Vec ColorImage (row-major order)
+pub fn slice_atlas(src: &ColorImage, cols: usize, rows: usize) ->
+  Result<Vec<ColorImage>, Box<dyn Error>> {
+  let [sw, sh] = src.size;
+  if cols == 0 || rows == 0 || sw % cols != 0 || sh % rows != 0 {
+    return Err(format!("atlas {}x{} is not divisible by {}x{}",
+      sw, sh, cols, rows).into());
+  }
+  let (w, h) = (sw / cols, sh / rows);
+  Ok((0..rows).flat_map(|r| (0..cols).map(move |c| (c, r)))
+    .map(|(c, r)| crop_color_image(src, c * w, r * h, w, h)).collect())
+}
+
+/// try create integer upscaled copy from ColorImage (for pixel art)
+/// - src: &amp;ColorImage
+/// - factor: usize (each pixel becomes factor x factor block)
+/// - result: Result ColorImage (Invalid when factor is 0 or too large)
+pub fn try_scale_integer(src: &ColorImage, factor: usize) ->
+  Result<ColorImage, ResourceError> {
+  let [w, h] = src.size;
+  let k = factor;
+  let err = || ResourceError::Invalid(format!("scale {}x{} by {}", w, h, k));
+  let (Some(dw), Some(dh)) = (w.checked_mul(k), h.checked_mul(k)) else {
+    return Err(err());
+  };
+  if k == 0 { return Err(err()); }
+  if dw > 0 && dh > 0 { validate_dims([dw, dh])?; } // empty src stays empty
+  Ok(remapped_from([dw, dh], src, |x, y| (y / k) * w + x / k))
+}
+
+/// create integer upscaled copy from ColorImage (for pixel art)
+/// - src: &amp;ColorImage
+/// - factor: usize (each pixel becomes factor x factor block, 0 as 1)
+/// - result: ColorImage (ColorImage::example() when too large)
+pub fn scale_integer(src: &ColorImage, factor: usize) -> ColorImage {
+  try_scale_integer(src, factor.max(1))
+    .unwrap_or_else(|_| ColorImage::example())
+}
+
+/// create tiled copy from ColorImage (repeat src to fill wh)
+/// - src: &amp;ColorImage (cropped when larger than wh)
+/// - wh: [usize; 2] (of the result, the last partial tiles are clipped)
+/// - result: ColorImage (transparent when src is empty)
+pub fn tile_color_image(src: &ColorImage, wh: [usize; 2]) -> ColorImage {
+  let [w, h] = src.size;
+  if w == 0 || h == 0 { return ColorImage::new(wh, Color32::TRANSPARENT); }
+  remapped_from(wh, src, |x, y| (y % h) * w + x % w)
+}
+
+/// try create padded copy from ColorImage
+/// - src: &amp;ColorImage
+/// - top: usize
+/// - right: usize
+/// - bottom: usize
+/// - left: usize
+/// - fill: Color32 (of the border)
+/// - result: Result ColorImage (size is expanded by the padding,
+///   Invalid when the size overflows or is too large)
+pub fn try_pad_color_image(src: &ColorImage,
+  top: usize, right: usize, bottom: usize, left: usize,
+  fill: Color32) -> Result<ColorImage, ResourceError> {
+  let [sw, sh] = src.size;
+  let add = |a: usize, b: usize, c: usize| a.checked_add(b)?.checked_add(c);
+  let (Some(w), Some(h)) = (add(left, sw, right), add(top, sh, bottom)) else {
+    return Err(ResourceError::Invalid(format!("pad {}x{} overflows", sw, sh)));
+  };
+  if w > 0 && h > 0 { validate_dims([w, h])?; } // empty src stays empty
+  let mut dst = ColorImage::new([w, h], fill);
+  for y in 0..sh {
+    let d = (top + y) * w + left;
+    dst.pixels[d..d + sw].copy_from_slice(&src.pixels[y * sw..(y + 1) * sw]);
+  }
+  Ok(dst)
+}
+
+/// create padded copy from ColorImage
+/// - src: &amp;ColorImage
+/// - top: usize
+/// - right: usize
+/// - bottom: usize
+/// - left: usize
+/// - fill: Color32 (of the border)
+/// - result: ColorImage (size is expanded by the padding,
+///   ColorImage::example() when too large)
+pub fn pad_color_image(src: &ColorImage,
+  top: usize, right: usize, bottom: usize, left: usize,
+  fill: Color32) -> ColorImage {
+  try_pad_color_image(src, top, right, bottom, left, fill)
+    .unwrap_or_else(|_| ColorImage::example())
+}
+
+/// source-over blend (premultiplied)
+/// - d: Color32 destination
+/// - s: Color32 source
+/// - result: Color32
+fn blend_over(d: Color32, s: Color32) -> Color32 {
+  let (d, s) = (d.to_array(), s.to_array());
+  let k = 255 - s[3] as u16;
+  let o = |i: usize| s[i].saturating_add(((d[i] as u16 * k + 127) / 255) as u8);
+  Color32::from_rgba_premultiplied(o(0), o(1), o(2), o(3))
+}
+
+/// overlay ColorImage onto base (source-over alpha blending)
+/// - base: &amp;mut ColorImage
+/// - overlay: &amp;ColorImage
+/// - x: usize left of overlay on base
+/// - y: usize top of overlay on base
+/// - result: () (clipped to base)
+pub fn overlay_color_image(base: &mut ColorImage, overlay: &ColorImage,
+  x: usize, y: usize) {
+  let ([bw, bh], [ow, oh]) = (base.size, overlay.size);
+  for j in 0..oh.min(bh.saturating_sub(y)) {
+    for i in 0..ow.min(bw.saturating_sub(x)) {
+      let d = &mut base.pixels[(y + j) * bw + x + i];
+      *d = blend_over(*d, overlay.pixels[j * ow + i]);
+    }
+  }
+}
+
+/// BlendMode (of a layer onto the layers below, premultiplied)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+  /// source-over
+  Normal,
+  /// darken (s * d, plus the uncovered parts of each)
+  Multiply,
+  /// lighten (s + d - s * d)
+  Screen,
+  /// additive (s + d, saturated)
+  Add
+}
+
+/// blend with BlendMode (premultiplied, the same formula on every channel)
+/// - d: Color32 destination
+/// - s: Color32 source
+/// - mode: BlendMode
+/// - result: Color32
+fn blend_with(d: Color32, s: Color32, mode: BlendMode) -> Color32 {
+  if mode == BlendMode::Normal { return blend_over(d, s); }
+  let (d, s) = (d.to_array(), s.to_array());
+  let m = |a: u8, b: u8| ((a as u16 * b as u16 + 127) / 255) as u8;
+  let o = |i: usize| match mode {
+  BlendMode::Multiply => m(s[i], d[i]).saturating_add(m(s[i], 255 - d[3]))
+    .saturating_add(m(d[i], 255 - s[3])),
+  BlendMode::Screen => s[i].saturating_add(d[i] - m(s[i], d[i])),
+  _ => s[i].saturating_add(d[i])
+  };
+  Color32::from_rgba_premultiplied(o(0), o(1), o(2), o(3))
+}
+
+/// compose ColorImage from layers with BlendMode (bottom first)
+/// - layers: &amp;[(ColorImage, [usize; 2], BlendMode)] (img, offset xy, mode)
+/// - result: ColorImage (transparent canvas from (0, 0) to the max of
+///   offset + size of the layers, 0x0 when empty,
+///   ColorImage::example() when a layer is inconsistent, the canvas
+///   overflows or is too large)
+pub fn composite(layers: &[(ColorImage, [usize; 2], BlendMode)]) ->
+  ColorImage {
+  let (mut w, mut h) = (0, 0);
+  for (img, o, _) in layers {
+    let (Some(r), Some(b)) = (o[0].checked_add(img.width()),
+      o[1].checked_add(img.height())) else { return ColorImage::example(); };
+    if validate(img).is_err() { return ColorImage::example(); }
+    (w, h) = (w.max(r), h.max(b));
+  }
+  if validate_dims([w.max(1), h.max(1)]).is_err() {
+    return ColorImage::example();
+  }
+  let mut dst = ColorImage::new([w, h], Color32::TRANSPARENT);
+  for (img, [x, y], mode) in layers {
+    let iw = img.width();
+    for j in 0..img.height() {
+      for i in 0..iw {
+        let d = &mut dst.pixels[(y + j) * w + x + i];
+        *d = blend_with(*d, img.pixels[j * iw + i], *mode);
+      }
+    }
+  }
+  dst
+}
+
+/// max channel diff between ColorImages
+/// - a: &amp;ColorImage
+/// - b: &amp;ColorImage
+/// - result: u8 (255 when sizes differ)
+pub fn max_channel_diff(a: &ColorImage, b: &ColorImage) -> u8 {
+  if a.size != b.size || a.pixels.len() != b.pixels.len() { return 255; }
+  a.pixels.iter().zip(b.pixels.iter()).flat_map(|(p, q)|
+    p.to_array().into_iter().zip(q.to_array()).map(|(c, d)| c.abs_diff(d))
+  ).max().unwrap_or(0)
+}
+
+/// histogram of ColorImage
+/// - src: &amp;ColorImage
+/// - result: [[u32; 256]; 4] (counts of r g b a, premultiplied values)
+pub fn histogram(src: &ColorImage) -> [[u32; 256]; 4] {
+  let mut hist = [[0u32; 256]; 4];
+  for c in src.pixels.iter() {
+    for (h, v) in hist.iter_mut().zip(c.to_array()) { h[v as usize] += 1; }
+  }
+  hist
+}
+
+/// mean color of ColorImage
+/// - src: &amp;ColorImage
+/// - result: Color32 (average of each channel, TRANSPARENT when empty)
+pub fn mean_color(src: &ColorImage) -> Color32 {
+  let n = src.pixels.len() as u64;
+  if n == 0 { return Color32::TRANSPARENT; }
+  let mut sum = [0u64; 4];
+  for c in src.pixels.iter() {
+    for (s, v) in sum.iter_mut().zip(c.to_array()) { *s += v as u64; }
+  }
+  let m = |i: usize| ((sum[i] + n / 2) / n) as u8;
+  Color32::from_rgba_premultiplied(m(0), m(1), m(2), m(3))
+}
+
+/// dominant color of ColorImage (most populous bucket, not washed out)
+/// - src: &amp;ColorImage
+/// - buckets: usize (per channel, clamped into 1..=256)
+/// - result: Color32 (average of the bucket, TRANSPARENT when no pixel)
+///
+/// fully transparent pixels are ignored
+pub fn dominant_color(src: &ColorImage, buckets: usize) -> Color32 {
+  let n = buckets.clamp(1, 256);
+  let q = |v: u8| v as usize * n / 256;
+  let mut bins: HashMap<[usize; 3], (u64, [u64; 4])> = HashMap::new();
+  for c in src.pixels.iter().filter(|c| c.a() > 0) {
+    let v = c.to_array();
+    let e = bins.entry([q(v[0]), q(v[1]), q(v[2])]).or_default();
+    e.0 += 1;
+    for (s, v) in e.1.iter_mut().zip(v) { *s += v as u64; }
+  }
+  let Some((_, (k, s))) = bins.into_iter()
+    .max_by(|(a, (ka, _)), (b, (kb, _))| ka.cmp(kb).then(b.cmp(a))) else {
+    return Color32::TRANSPARENT;
+  };
+  let m = |i: usize| ((s[i] + k / 2) / k) as u8;
+  Color32::from_rgba_premultiplied(m(0), m(1), m(2), m(3))
+}
+
+/// compare ColorImages within tolerance (for snapshot tests)
+/// - a: &amp;ColorImage
+/// - b: &amp;ColorImage
+/// - tolerance: u8 (max diff allowed for every channel)
+/// - result: bool (false when sizes differ)
+pub fn compare_color_images(a: &ColorImage, b: &ColorImage,
+  tolerance: u8) -> bool {
+  a.size == b.size && max_channel_diff(a, b) <= tolerance
+}
+
+/// create ColorImage from memory
+/// - bytes: &amp;[u8] encoded image (png, jpeg, etc)
+/// - result: Result ColorImage
+pub fn color_image_from_memory(bytes: &[u8]) ->
+  Result<ColorImage, image::ImageError> {
+  Ok(color_image_from_dynamic_image(load_from_memory(bytes)?))
+}
+
+/// create ColorImage from data uri
+/// - uri: &amp;str (data:image/png;base64,...)
+/// - result: Result ColorImage
+#[cfg(feature = "datauri")]
+pub fn color_image_from_data_uri(uri: &str) ->
+  Result<ColorImage, Box<dyn Error>> {
+  use base64::Engine;
+  let Some(body) = uri.trim().strip_prefix("data:") else {
+    return Err("data uri must start with data:".into());
+  };
+  let Some((meta, payload)) = body.split_once(',') else {
+    return Err("data uri has no ',' before the payload".into());
+  };
+  let Some(mime) = meta.strip_suffix(";base64") else {
+    return Err(format!("data uri is not base64 encoded: {}", meta).into());
+  };
+  if !mime.starts_with("image/") {
+    return Err(format!("data uri is not an image: {}", mime).into());
+  }
+  let b = base64::engine::general_purpose::STANDARD.decode(payload)?;
+  Ok(color_image_from_memory(&b)?)
+}
+
+/// create ColorImage from reader (format is guessed from the content)
+/// - r: R: Read + Seek (socket, decompression stream, etc)
+/// - result: Result ColorImage
+pub fn color_image_from_reader<R: Read + Seek>(r: R) ->
+  Result<ColorImage, Box<dyn Error>> {
+  let rdr = image::io::Reader::new(BufReader::new(r)).with_guessed_format()?;
+  Ok(color_image_from_dynamic_image(rdr.decode()?))
+}
+
+/// validate ColorImage (size and pixels.len() must be consistent)
+/// - img: &amp;ColorImage
+/// - result: Result () or descriptive message
+pub fn validate(img: &ColorImage) -> Result<(), String> {
+  let [w, h] = img.size;
+  match w.checked_mul(h) {
+  Some(n) if n == img.pixels.len() => Ok(()),
+  Some(n) => Err(format!("size {}x{} needs {} pixels but has {}",
+    w, h, n, img.pixels.len())),
+  None => Err(format!("size {}x{} overflows", w, h))
+  }
+}
+
+/// convert ColorImage into ImageData (for Context::load_texture)
+/// - img: ColorImage (move, pixels are not copied)
+/// - result: ImageData (ColorImage::example() when size is zero or mismatch)
+pub fn into_image_data(img: ColorImage) -> ImageData {
+  if img.pixels.is_empty() || validate(&img).is_err() {
+    return ImageData::from(ColorImage::example());
+  }
+  ImageData::from(img)
+}
+
+/// create ImageData from &amp;ColorImage (for Context::load_texture)
+/// - img: &amp;ColorImage (pixels are copied)
+/// - result: ImageData (ColorImage::example() when size is zero or mismatch)
+pub fn image_data_from(img: &ColorImage) -> ImageData {
+  into_image_data(img.clone())
+}
+
+/// create ColorImage from ImageData (back from into_image_data)
+/// - data: &amp;ImageData
+/// - result: Option ColorImage (Font coverage becomes premultiplied white
+///   with the coverage as alpha, no gamma)
+pub fn color_image_from_image_data(data: &ImageData) -> Option<ColorImage> {
+  match data {
+  ImageData::Color(img) => Some(img.clone()),
+  ImageData::Font(img) => Some(ColorImage{size: img.size,
+    pixels: img.pixels.iter().map(|c| {
+      let a = (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+      Color32::from_rgba_premultiplied(a, a, a, a)
+    }).collect()})
+  }
+}
+
+/// decode progressively (rows of non interlaced png arrive in order,
+/// interlaced png is called back after each adam7 pass)
+/// - bytes: &amp;[u8] encoded image (png, jpeg, etc)
+/// - on_partial: FnMut(&amp;ColorImage) (called with the decoded pixels so far,
+///   not decoded pixels are transparent, the last call has the whole image)
+/// - result: Result ColorImage
+///
+/// other formats (progressive jpeg too) are called back once at the end
+pub fn decode_progressive(bytes: &[u8],
+  mut on_partial: impl FnMut(&ColorImage)) ->
+  Result<ColorImage, Box<dyn Error>> {
+  if image::guess_format(bytes).ok() == Some(ImageFormat::Png) {
+    let mut dec = png::Decoder::new(bytes);
+    dec.set_transformations(png::Transformations::normalize_to_color8());
+    let mut rdr = dec.read_info()?;
+    let (w, h) = (rdr.info().width as usize, rdr.info().height as usize);
+    let (ct, _) = rdr.output_color_type();
+    let px = |d: &[u8], x: usize| match ct {
+    png::ColorType::Grayscale => Color32::from_gray(d[x]),
+    png::ColorType::GrayscaleAlpha =>
+      Color32::from_rgba_unmultiplied(d[x*2], d[x*2], d[x*2], d[x*2+1]),
+    png::ColorType::Rgb => Color32::from_rgb(d[x*3], d[x*3+1], d[x*3+2]),
+    _ => Color32::from_rgba_unmultiplied(d[x*4], d[x*4+1], d[x*4+2], d[x*4+3])
+    };
+    let mut img = ColorImage::new([w, h], Color32::TRANSPARENT);
+    if !rdr.info().interlaced {
+      let step = (h / 8).max(1); // about 8 partial updates
+      let mut y = 0;
+      while let Some(row) = rdr.next_row()? {
+        let d = row.data();
+        for (x, p) in img.pixels[y * w..(y + 1) * w].iter_mut().enumerate() {
+          *p = px(d, x);
+        }
+        y += 1;
+        if y >= h { break; }
+        if y % step == 0 { on_partial(&img); }
+      }
+      on_partial(&img);
+      return Ok(img);
+    }
+    // adam7 passes (x start, x step, y start, y step), empty ones are skipped
+    const ADAM7: [(usize, usize, usize, usize); 7] = [(0, 8, 0, 8),
+      (4, 8, 0, 8), (0, 4, 4, 8), (2, 4, 0, 4), (0, 2, 2, 4), (1, 2, 0, 2),
+      (0, 1, 1, 2)];
+    for (xs, xd, ys, yd) in ADAM7 {
+      let pw = w.saturating_sub(xs).div_ceil(xd);
+      let ph = h.saturating_sub(ys).div_ceil(yd);
+      if pw == 0 || ph == 0 { continue; }
+      for j in 0..ph {
+        let row = rdr.next_interlaced_row()?.ok_or("png rows end early")?;
+        let (d, y) = (row.data(), ys + j * yd);
+        for i in 0..pw { img.pixels[y * w + xs + i * xd] = px(d, i); }
+      }
+      on_partial(&img);
+    }
+    return Ok(img);
+  }
+  let img = color_image_from_memory(bytes)?;
+  on_partial(&img);
+  Ok(img)
+}
+
+/// create icon from memory
+/// - bytes: &amp;[u8] encoded image (png, jpeg, etc)
+/// - result: Option eframe::IconData
+pub fn icon_from_memory(bytes: &[u8]) -> Option<eframe::IconData> {
+  let img = load_from_memory(bytes).ok()?;
+  let (rgba, width, height) = im_flat!(img);
+  Some(eframe::IconData{rgba, width, height})
+}
+
+/// name of the density specific asset (@Nx is inserted before the extension)
+/// - f: &amp;str filename (icons/icon.png)
+/// - scale: u32
+/// - result: String (icons/icon@2x.png, icon@2x without extension)
+fn scaled_name(f: &str, scale: u32) -> String {
+  let s = f.rfind(['/', '\\']).map_or(0, |i| i + 1);
+  match f[s..].rfind('.') {
+  Some(d) if d > 0 => format!("{}@{}x{}", &f[..s + d], scale, &f[s + d..]),
+  _ => format!("{}@{}x", f, scale)
+  }
+}
+
+/// extension matches one of exts (case insensitive)
+/// - f: &amp;str filename
+/// - exts: &amp;[&amp;str] (png, .png, etc, empty: any)
+/// - result: bool
+fn has_ext(f: &str, exts: &[&str]) -> bool {
+  if exts.is_empty() { return true; }
+  let Some(x) = Path::new(f).extension().and_then(|x| x.to_str()) else {
+    return false;
+  };
+  exts.iter().any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(x))
+}
+
+/// entry name is a file under subdir with the extension
+/// - n: &amp;str entry name ('/' separated, directory ends with '/')
+/// - subdir: &amp;str ("" for all)
+/// - exts: &amp;[&amp;str] (png, .png, etc, empty: any)
+/// - result: bool
+#[cfg(any(feature = "zip", feature = "rust-embed"))]
+fn listed_entry(n: &str, subdir: &str, exts: &[&str]) -> bool {
+  let d = subdir.trim_matches('/');
+  !n.ends_with('/') && has_ext(n, exts) &&
+    (d.is_empty() || n.strip_prefix(d).is_some_and(|r| r.starts_with('/')))
+}
+
+/// reg font data into FontDefinitions
+/// - fonts: &amp;mut FontDefinitions
+/// - n: &amp;str name
+/// - fd: FontData (move)
+/// - t: FontFamily family (move)
+/// - append: bool (true: push to the back false: insert at the front)
+/// - result: () (the place in the family is kept when n is already there)
+fn reg_font_data(fonts: &mut FontDefinitions,
+  n: &str, fd: FontData, t: FontFamily, append: bool) {
+  let n = n.to_string();
+  let m = n.clone();
+  fonts.font_data.insert(n, fd);
+  let family = fonts.families.entry(t).or_default();
+  if family.contains(&m) { return; }
+  if append { family.push(m); } else { family.insert(0, m); }
+}
+
+/// merge FontDefinitions (without clobbering base)
+/// - base: &amp;mut FontDefinitions
+/// - extra: FontDefinitions (move)
+/// - result: ()
+///
+/// font_data of base is kept for the same name,
+/// names of each family in extra are appended when base does not have them
+pub fn merge_fonts(base: &mut FontDefinitions, extra: FontDefinitions) {
+  for (n, fd) in extra.font_data.into_iter() {
+    base.font_data.entry(n).or_insert(fd);
+  }
+  for (t, names) in extra.families.into_iter() {
+    let family = base.families.entry(t).or_default();
+    for n in names.into_iter() {
+      if !family.contains(&n) { family.push(n); }
+    }
+  }
+}
+
+/// ResourceError
+#[derive(Debug)]
+pub enum ResourceError {
+  /// io error (read, archive, network, etc)
+  Io(std::io::Error),
+  /// decode error
+  Decode(image::ImageError),
+  /// file (or entry) is not found
+  NotFound(PathBuf),
+  /// content is invalid for the request (broken container, etc)
+  Invalid(String)
+}
+
+/// ResourceError
+impl ResourceError {
+  /// from io error (NotFound for ErrorKind::NotFound)
+  /// - e: std::io::Error (move)
+  /// - p: &amp;Path
+  /// - result: ResourceError
+  fn from_io(e: std::io::Error, p: &Path) -> Self {
+    match e.kind() {
+    std::io::ErrorKind::NotFound => ResourceError::NotFound(p.to_path_buf()),
+    _ => ResourceError::Io(e)
+    }
+  }
+}
+
+/// Display for ResourceError
+impl std::fmt::Display for ResourceError {
+  /// fmt
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+    ResourceError::Io(e) => write!(f, "io error: {}", e),
+    ResourceError::Decode(e) => write!(f, "decode error: {}", e),
+    ResourceError::NotFound(p) => write!(f, "not found: {}", p.display()),
+    ResourceError::Invalid(s) => write!(f, "invalid: {}", s)
+    }
+  }
+}
+
+/// Error for ResourceError
+impl Error for ResourceError {
+  /// source
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+    ResourceError::Io(e) => Some(e),
+    ResourceError::Decode(e) => Some(e),
+    ResourceError::NotFound(_) | ResourceError::Invalid(_) => None
+    }
+  }
+}
+
+/// From io::Error for ResourceError
+impl From<std::io::Error> for ResourceError {
+  /// from
+  fn from(e: std::io::Error) -> Self { ResourceError::Io(e) }
+}
+
+/// From ImageError for ResourceError
+impl From<image::ImageError> for ResourceError {
+  /// from
+  fn from(e: image::ImageError) -> Self { ResourceError::Decode(e) }
+}
+
+/// ManifestEntry (an element of the json array for load_manifest)
+#[cfg(feature = "manifest")]
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ManifestEntry {
+  /// logical name (key of the result or font name)
+  pub name: String,
+  /// filename relative to the base of the provider
+  pub file: String,
+  /// "image" (default) or "font"
+  #[serde(rename = "type", default = "ManifestEntry::image")]
+  pub kind: String,
+  /// "proportional" (default) or "monospace" for font
+  #[serde(default)]
+  pub family: Option<String>
+}
+
+/// ManifestEntry
+#[cfg(feature = "manifest")]
+impl ManifestEntry {
+  /// default kind
+  fn image() -> String { "image".to_string() }
+}
+
+/// Location of the resource (typed alternative of p: bool)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+  /// relative to the base of the provider (p: true)
+  Relative,
+  /// full path (p: false)
+  Absolute
+}
+
+/// From bool for Location
+impl From<bool> for Location {
+  /// from (true: Relative false: Absolute)
+  fn from(p: bool) -> Self {
+    if p { Location::Relative } else { Location::Absolute }
+  }
+}
+
+/// From Location for bool
+impl From<Location> for bool {
+  /// from (Relative: true Absolute: false)
+  fn from(l: Location) -> Self { l == Location::Relative }
+}
+
+/// ResourceProvider (decode logic is shared by default methods)
+///
+/// read_bytes_at is the method to implement, callers should use *_at
+#[allow(deprecated)] // default methods are built on the bool shims
+pub trait ResourceProvider {
+  /// read bytes at Location
+  /// - f: &amp;str filename
+  /// - l: Location (Relative: the provider base Absolute: full path)
+  /// - result: Result Vec&lt;u8&gt;
+  fn read_bytes_at(&self, f: &str, l: Location) ->
+    Result<Vec<u8>, ResourceError>;
+
+  /// read bytes
+  /// - f: &amp;str filename
+  /// - p: bool (true: relative to the provider base false: full path)
+  /// - result: Result Vec u8
+  #[deprecated(note = "use read_bytes_at with Location")]
+  fn read_bytes(&self, f: &str, p: bool) ->
+    Result<Vec<u8>, ResourceError> {
+    self.read_bytes_at(f, p.into())
+  }
+
+  /// read bytes into the buffer (reuse the allocation)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - buf: &amp;mut Vec&lt;u8&gt; (cleared then filled)
+  /// - result: Result ()
+  fn read_bytes_into(&self, f: &str, p: bool, buf: &mut Vec<u8>) ->
+    Result<(), ResourceError> {
+    buf.clear();
+    buf.extend_from_slice(&self.read_bytes_at(f, p.into())?);
+    Ok(())
+  }
+
+  /// load resource img at Location
+  /// - f: &amp;str filename
+  /// - l: Location
+  /// - result: ColorImage (ColorImage::example() when failed)
+  fn resource_img_at(&self, f: &str, l: Location) -> ColorImage {
+    self.resource_img(f, l.into())
+  }
+
+  /// resource exists (default reads the bytes, providers may override)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: bool
+  fn exists(&self, f: &str, p: bool) -> bool {
+    self.read_bytes(f, p).is_ok()
+  }
+
+  /// modification time of the resource (default is not supported)
+  /// - _f: &amp;str filename
+  /// - _p: bool (true: base of the provider false: full path)
+  /// - result: Result SystemTime
+  fn modified(&self, _f: &str, _p: bool) -> Result<SystemTime, ResourceError> {
+    Err(ResourceError::Invalid("modified is not supported".to_string()))
+  }
+
+  /// list resource files (default is not supported)
+  /// - _subdir: &amp;str sub directory ("" for all)
+  /// - _exts: &amp;[&amp;str] (png, .png, etc, case insensitive, empty: any)
+  /// - result: Result Vec PathBuf (names usable as f with p = true)
+  fn list_resources(&self, _subdir: &str, _exts: &[&str]) ->
+    Result<Vec<PathBuf>, ResourceError> {
+    Err(ResourceError::Invalid("list_resources is not supported".to_string()))
+  }
+
+  /// load resource img with the modification time
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Result (ColorImage, SystemTime)
+  fn resource_img_with_mtime(&self, f: &str, p: bool) ->
+    Result<(ColorImage, SystemTime), ResourceError> {
+    let t = self.modified(f, p)?;
+    Ok((self.try_resource_img(f, p)?, t))
+  }
+
+  /// load resource img
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: ColorImage (ColorImage::example() when failed)
+  #[deprecated(note = "use resource_img_at with Location")]
+  fn resource_img(&self, f: &str, p: bool) -> ColorImage {
+    self.try_resource_img(f, p).unwrap_or_else(|_| ColorImage::example())
+  }
+
+  /// load resource img with fallback
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - fallback: &amp;ColorImage (cloned when failed)
+  /// - result: ColorImage
+  fn resource_img_or(&self, f: &str, p: bool, fallback: &ColorImage) ->
+    ColorImage {
+    self.try_resource_img(f, p).unwrap_or_else(|_| fallback.clone())
+  }
+
+  /// load resource imgs into HashMap keyed by name
+  /// - names: &amp;[&amp;str] filenames
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: HashMap (ColorImage::example() for the failed names)
+  fn load_img_batch(&self, names: &[&str], p: bool) ->
+    HashMap<String, ColorImage> {
+    names.iter().map(|&n| (n.to_string(), self.resource_img(n, p))).collect()
+  }
+
+  /// load resource imgs into HashMap keyed by name in parallel
+  /// - names: &amp;[&amp;str] filenames
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: HashMap (ColorImage::example() for the failed names)
+  #[cfg(feature = "rayon")]
+  fn load_img_batch_par(&self, names: &[&str], p: bool) ->
+    HashMap<String, ColorImage> where Self: Sync {
+    use rayon::prelude::*;
+    names.par_iter().map(|&n| (n.to_string(), self.resource_img(n, p)))
+      .collect()
+  }
+
+  /// spawn loading resource img on a background thread (with a clone)
+  /// - f: String filename (move)
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: JoinHandle of Result ColorImage (poll is_finished then join)
+  fn spawn_load(&self, f: String, p: bool) ->
+    std::thread::JoinHandle<Result<ColorImage, ResourceError>>
+    where Self: Clone + Send + 'static {
+    let r = self.clone();
+    std::thread::spawn(move || r.try_resource_img(&f, p))
+  }
+
+  /// try load resource imgs into HashMap keyed by name
+  /// - names: &amp;[&amp;str] filenames
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: (loaded HashMap, Vec of (name, ResourceError) for the failed)
+  fn try_load_img_batch(&self, names: &[&str], p: bool) ->
+    (HashMap<String, ColorImage>, Vec<(String, ResourceError)>) {
+    let mut imgs = HashMap::new();
+    let mut errs = vec![];
+    for &n in names {
+      match self.try_resource_img(n, p) {
+      Ok(img) => { imgs.insert(n.to_string(), img); },
+      Err(e) => errs.push((n.to_string(), e))
+      }
+    }
+    (imgs, errs)
+  }
+
+  /// try load resource img
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Result ColorImage
+  fn try_resource_img(&self, f: &str, p: bool) ->
+    Result<ColorImage, ResourceError> {
+    let b = self.read_bytes(f, p)?;
+    Ok(color_image_from_memory(&b)?)
+  }
+
+  /// load resource img only when width * height &lt;= max_pixels
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - max_pixels: usize (checked by the header before decoding)
+  /// - result: Result ColorImage (Invalid when too large)
+  fn resource_img_capped(&self, f: &str, p: bool, max_pixels: usize) ->
+    Result<ColorImage, ResourceError> {
+    let (w, h) = self.resource_dimensions(f, p)?;
+    match (w as usize).checked_mul(h as usize) {
+    Some(n) if n <= max_pixels => self.try_resource_img(f, p),
+    _ => Err(ResourceError::Invalid(format!("{} {}x{} exceeds {} pixels",
+      f, w, h, max_pixels)))
+    }
+  }
+
+  /// resource dimensions (decode only the header)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Result (width, height)
+  fn resource_dimensions(&self, f: &str, p: bool) ->
+    Result<(u32, u32), ResourceError> {
+    let b = self.read_bytes(f, p)?;
+    let r = image::io::Reader::new(Cursor::new(b)).with_guessed_format()?;
+    Ok(r.into_dimensions()?)
+  }
+
+  /// detect format of the resource from the content (not the extension)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Result image::ImageFormat (Decode error when unknown)
+  fn detect_format(&self, f: &str, p: bool) ->
+    Result<ImageFormat, ResourceError> {
+    let b = self.read_bytes(f, p)?;
+    Ok(image::guess_format(&b)?)
+  }
+
+  /// load resource img for the density (@2x @3x assets by convention)
+  /// - base_name: &amp;str filename (icon.png)
+  /// - scale: u32 (2 tries icon@2x.png then icon.png)
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: ColorImage (ColorImage::example() when failed)
+  ///
+  /// @Nx is inserted before the extension of the file name
+  /// (icons/icon.png to icons/icon@3x.png, icon to icon@3x),
+  /// tried from scale down to 2 then base_name
+  fn resource_img_scaled(&self, base_name: &str, scale: u32, p: bool) ->
+    ColorImage {
+    (2..=scale).rev().find_map(|n|
+      self.try_resource_img(&scaled_name(base_name, n), p).ok()
+    ).unwrap_or_else(|| self.resource_img(base_name, p))
+  }
+
+  /// load resource img oriented by the EXIF orientation (photos)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Result ColorImage (as is without EXIF or orientation 1)
+  #[cfg(feature = "exif")]
+  fn resource_img_oriented(&self, f: &str, p: bool) ->
+    Result<ColorImage, ResourceError> {
+    let b = self.read_bytes(f, p)?;
+    let img = color_image_from_memory(&b)?;
+    let o = exif::Reader::new().read_from_container(&mut Cursor::new(&b))
+      .ok().and_then(|e| e.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+        .and_then(|fd| fd.value.get_uint(0)));
+    Ok(oriented_color_image(&img, o.unwrap_or(1)))
+  }
+
+  /// load resource img resized (without the full size ColorImage)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - wh: [usize; 2] (to be resized, 0 is clamped to 1)
+  /// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+  /// - result: ColorImage (ColorImage::example() when failed)
+  fn resource_img_resized(&self, f: &str, p: bool, wh: [usize; 2],
+    filter: FilterType) -> ColorImage {
+    let Ok(b) = self.read_bytes(f, p) else { return ColorImage::example(); };
+    let Ok(img) = load_from_memory(&b) else { return ColorImage::example(); };
+    let Ok((w, h)) = validate_dims([wh[0].max(1), wh[1].max(1)]) else {
+      return ColorImage::example();
+    };
+    color_image_from_dynamic_image(img.resize_to_fill(w, h, filter))
+  }
+
+  /// load resource mask (single channel)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Result (Vec&lt;u8&gt; alpha, or luma when src has no alpha, wh)
+  fn resource_mask(&self, f: &str, p: bool) ->
+    Result<(Vec<u8>, [usize; 2]), ResourceError> {
+    let img = load_from_memory(&self.read_bytes(f, p)?)?;
+    let wh = [img.width() as usize, img.height() as usize];
+    let mask = match img.color().has_alpha() {
+    true => img.into_rgba8().pixels().map(|c| c.0[3]).collect(),
+    false => img.into_luma8().into_raw()
+    };
+    Ok((mask, wh))
+  }
+
+  /// load resource img with format (not guessed from the content)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - fmt: image::ImageFormat (Png, Ico, WebP, etc)
+  /// - result: ColorImage (ColorImage::example() when failed)
+  fn resource_img_with_format(&self, f: &str, p: bool, fmt: ImageFormat) ->
+    ColorImage {
+    let Ok(b) = self.read_bytes(f, p) else { return ColorImage::example(); };
+    match image::load_from_memory_with_format(&b, fmt) {
+    Ok(img) => color_image_from_dynamic_image(img),
+    Err(_) => ColorImage::example()
+    }
+  }
+
+  /// load texture (read decode and upload)
+  /// - ctx: &amp;Context
+  /// - name: &amp;str texture name
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - options: TextureOptions (NEAREST, LINEAR)
+  /// - result: TextureHandle
+  fn load_texture(&self, ctx: &Context, name: &str, f: &str, p: bool,
+    options: TextureOptions) -> TextureHandle {
+    ctx.load_texture(name, into_image_data(self.resource_img(f, p)), options)
+  }
+
+  /// load resource frames (animated gif or apng)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Result Vec (ColorImage, Duration) (delay of each frame)
+  ///
+  /// not animated image returns a single frame with zero duration
+  fn resource_frames(&self, f: &str, p: bool) ->
+    Result<Vec<(ColorImage, Duration)>, ResourceError> {
+    let b = self.read_bytes(f, p)?;
+    let frames = match image::guess_format(&b)? {
+    ImageFormat::Gif => GifDecoder::new(Cursor::new(&b))?.into_frames(),
+    ImageFormat::Png => {
+      let dec = PngDecoder::new(Cursor::new(&b))?;
+      if !dec.is_apng() {
+        return Ok(vec![(color_image_from_memory(&b)?, Duration::ZERO)]);
+      }
+      dec.apng().into_frames()
+    },
+    _ => return Ok(vec![(color_image_from_memory(&b)?, Duration::ZERO)])
+    };
+    Ok(frames.collect_frames()?.into_iter().map(|fr| {
+      let d = Duration::from(fr.delay());
+      (color_image_from_dynamic_image(fr.into_buffer().into()), d)
+    }).collect())
+  }
+
+  /// load resource ico frame best matching desired size
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - desired: u32 (nearest not smaller frame, else the largest)
+  /// - result: Result ColorImage
+  fn resource_ico_frame(&self, f: &str, p: bool, desired: u32) ->
+    Result<ColorImage, ResourceError> {
+    let b = self.read_bytes(f, p)?;
+    let inv = || ResourceError::Invalid(format!("broken ico: {}", f));
+    if b.len() < 6 || b[0..4] != [0, 0, 1, 0] { return Err(inv()); }
+    let n = u16::from_le_bytes([b[4], b[5]]) as usize;
+    let d = |v: u8| if v == 0 { 256 } else { v as u32 }; // 0 means 256
+    let entries = (0..n).filter_map(|i| b.get(6 + i * 16..6 + (i + 1) * 16))
+      .map(|e| (d(e[0]).max(d(e[1])), e)).collect::<Vec<_>>();
+    let (_, e) = entries.iter().filter(|(s, _)| *s >= desired)
+      .min_by_key(|(s, _)| *s)
+      .or_else(|| entries.iter().max_by_key(|(s, _)| *s)).ok_or_else(inv)?;
+    let u = |i: usize| u32::from_le_bytes([e[i], e[i+1], e[i+2], e[i+3]]);
+    let (len, off) = (u(8) as usize, u(12) as usize);
+    let data = b.get(off..off.saturating_add(len)).ok_or_else(inv)?;
+    // rebuild ico with the single entry so the decoder takes only this frame
+    let mut ico = vec![0, 0, 1, 0, 1, 0];
+    ico.extend_from_slice(&e[..12]);
+    ico.extend_from_slice(&22u32.to_le_bytes());
+    ico.extend_from_slice(data);
+    let img = image::load_from_memory_with_format(&ico, ImageFormat::Ico)?;
+    Ok(color_image_from_dynamic_image(img))
+  }
+
+  /// load images and fonts listed in the json manifest
+  /// - manifest_file: &amp;str filename (relative to the base of the provider)
+  /// - fonts: &amp;mut FontDefinitions (font entries are registered)
+  /// - result: Result HashMap of images keyed by name
+  ///
+  /// manifest is an array of ManifestEntry
+  /// [{"name": "logo", "file": "logo.png"},
+  ///  {"name": "fira", "file": "FiraSans-Regular.ttf", "type": "font"}]
+  #[cfg(feature = "manifest")]
+  fn load_manifest(&self, manifest_file: &str, fonts: &mut FontDefinitions) ->
+    Result<HashMap<String, ColorImage>, ResourceError> {
+    let b = self.read_bytes(manifest_file, true)?;
+    let entries: Vec<ManifestEntry> = serde_json::from_slice(&b).map_err(|e|
+      ResourceError::Invalid(format!("{}: {}", manifest_file, e)))?;
+    let mut imgs = HashMap::new();
+    for e in entries {
+      match e.kind.as_str() {
+      "image" => {
+        imgs.insert(e.name, self.try_resource_img(&e.file, true)?);
+      },
+      "font" => {
+        let t = match e.family.as_deref() {
+        Some("monospace") => FontFamily::Monospace,
+        _ => FontFamily::Proportional
+        };
+        let fd = FontData::from_owned(self.read_bytes(&e.file, true)?);
+        reg_font_data(fonts, &e.name, fd, t, false);
+      },
+      k => return Err(ResourceError::Invalid(
+        format!("{}: unknown type {} of {}", manifest_file, k, e.name)))
+      }
+    }
+    Ok(imgs)
+  }
+
+  /// load resource svg (rasterize)
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - wh: [usize; 2] (0 for one side keeps the svg intrinsic aspect)
+  /// - result: ColorImage (ColorImage::example() when failed or the size is
+  ///   zero, NaN or too large)
+  #[cfg(feature = "svg")]
+  fn resource_svg(&self, f: &str, p: bool, wh: [usize; 2]) -> ColorImage {
+    use resvg::{usvg, tiny_skia};
+    let Ok(b) = self.read_bytes(f, p) else { return ColorImage::example(); };
+    let opt = usvg::Options::default();
+    let Ok(tree) = usvg::Tree::from_data(&b, &opt) else {
+      return ColorImage::example();
+    };
+    let (sw, sh) = (tree.size().width(), tree.size().height());
+    let (w, h) = match wh {
+    [0, 0] => (sw.round(), sh.round()),
+    [0, h] => ((h as f32 * sw / sh).round(), h as f32),
+    [w, 0] => (w as f32, (w as f32 * sh / sw).round()),
+    [w, h] => (w as f32, h as f32)
+    };
+    let Ok((pw, ph)) = validate_dims([w as usize, h as usize]) else {
+      return ColorImage::example(); // NaN as usize is 0
+    };
+    let Some(mut pixmap) = tiny_skia::Pixmap::new(pw, ph) else {
+      return ColorImage::example();
+    };
+    let t = tiny_skia::Transform::from_scale(w / sw, h / sh);
+    resvg::render(&tree, t, &mut pixmap.as_mut());
+    let size = [pixmap.width() as usize, pixmap.height() as usize];
+    let pixels = pixmap.data().chunks_exact(4).map(|c|
+      Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3]) // tiny_skia
+    ).collect::<Vec<_>>();
+    ColorImage{size, pixels}
+  }
+
+  /// load resource icon
+  /// - ico: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Option eframe::IconData
+  #[deprecated(note = "use resource_icon_at with Location")]
+  fn resource_icon(&self, ico: &str, p: bool) -> Option<eframe::IconData> {
+    let b = self.read_bytes(ico, p).ok()?;
+    icon_from_memory(&b)
+  }
+
+  /// load resource icon at Location
+  /// - ico: &amp;str filename
+  /// - l: Location
+  /// - result: Option eframe::IconData
+  fn resource_icon_at(&self, ico: &str, l: Location) ->
+    Option<eframe::IconData> {
+    self.resource_icon(ico, l.into())
+  }
+
+  /// load resource icon resized
+  /// - ico: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - wh: [usize; 2] (to be resized)
+  /// - filter: image::imageops::FilterType (Nearest, Lanczos3, etc)
+  /// - result: Option eframe::IconData (alpha is kept as rgba8,
+  ///   None when wh is zero or too large)
+  fn resource_icon_sized(&self, ico: &str, p: bool,
+    wh: [usize; 2], filter: FilterType) -> Option<eframe::IconData> {
+    let (w, h) = validate_dims(wh).ok()?;
+    let Ok(b) = self.read_bytes(ico, p) else { return None; };
+    if let Ok(img) = load_from_memory(&b) {
+      let img = img.resize_to_fill(w, h, filter);
+      let (rgba, width, height) = im_flat!(img);
+      Some(eframe::IconData{rgba, width, height})
+    }else{
+      None
+    }
+  }
+
+  /// load resource icon set (decode once, resize for each size)
+  /// - ico: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - sizes: &amp;[u32] (square edges 16, 32, 48, 256, etc)
+  /// - result: Vec eframe::IconData (empty when failed)
+  ///
+  /// resized with FilterType::Lanczos3, sizes larger than the source upscale,
+  /// invalid sizes (0) are skipped
+  fn resource_icon_set(&self, ico: &str, p: bool, sizes: &[u32]) ->
+    Vec<eframe::IconData> {
+    let Ok(b) = self.read_bytes(ico, p) else { return vec![]; };
+    let Ok(img) = load_from_memory(&b) else { return vec![]; };
+    sizes.iter().filter_map(|&sz| {
+      let (sz, _) = validate_dims([sz as usize, sz as usize]).ok()?;
+      let (rgba, width, height) = im_flat!(
+        img.resize_to_fill(sz, sz, FilterType::Lanczos3));
+      Some(eframe::IconData{rgba, width, height})
+    }).collect()
+  }
+
+  /// load resource font
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - f: &amp;str filename
+  /// - t: FontFamily family (move)
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: Option String (registered name, None when failed to read)
+  #[deprecated(note = "use resource_font_at with Location")]
+  fn resource_font(&self, fonts: &mut FontDefinitions,
+    n: &str, f: &str, t: FontFamily, p: bool) -> Option<String> {
+    let b = self.read_bytes(f, p).ok()?;
+    // use resource_font_static to embed with include_bytes!
+    reg_font_data(fonts, n, FontData::from_owned(b), t, false);
+    Some(n.to_string())
+  }
+
+  /// load resource font at Location
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - f: &amp;str filename
+  /// - t: FontFamily family (move)
+  /// - l: Location
+  /// - result: Option String (registered name, None when failed to read)
+  fn resource_font_at(&self, fonts: &mut FontDefinitions,
+    n: &str, f: &str, t: FontFamily, l: Location) -> Option<String> {
+    self.resource_font(fonts, n, f, t, l.into())
+  }
+
+  /// load resource font for both Proportional and Monospace (read once)
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - f: &amp;str filename
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: ()
+  fn resource_font_all(&self, fonts: &mut FontDefinitions,
+    n: &str, f: &str, p: bool) {
+    let Ok(b) = self.read_bytes(f, p) else { return; };
+    reg_font_data(fonts, n, FontData::from_owned(b),
+      FontFamily::Proportional, false);
+    let family = fonts.families.entry(FontFamily::Monospace).or_default();
+    if !family.iter().any(|m| m == n) { family.insert(0, n.to_string()); }
+  }
+
+  /// load resource font from bytes (without file IO)
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - data: Vec&lt;u8&gt; font bytes (move)
+  /// - t: FontFamily family (move)
+  /// - result: ()
+  fn resource_font_bytes(&self, fonts: &mut FontDefinitions,
+    n: &str, data: Vec<u8>, t: FontFamily) {
+    reg_font_data(fonts, n, FontData::from_owned(data), t, false);
+  }
+
+  /// load resource font from static bytes (without file IO)
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - data: &amp;'static [u8] (include_bytes!("path from src"))
+  /// - t: FontFamily family (move)
+  /// - result: ()
+  fn resource_font_static(&self, fonts: &mut FontDefinitions,
+    n: &str, data: &'static [u8], t: FontFamily) {
+    reg_font_data(fonts, n, FontData::from_static(data), t, false);
+  }
+
+  /// load resource font (append to the back of the family as a fallback)
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - f: &amp;str filename
+  /// - t: FontFamily family (move)
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: ()
+  fn resource_font_append(&self, fonts: &mut FontDefinitions,
+    n: &str, f: &str, t: FontFamily, p: bool) {
+    let Ok(b) = self.read_bytes(f, p) else { return; };
+    reg_font_data(fonts, n, FontData::from_owned(b), t, true);
+  }
+
+  /// load resource font selecting a face in a collection (.ttc)
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - f: &amp;str filename
+  /// - t: FontFamily family (move)
+  /// - index: u32 face index in the collection (0 for the first face)
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: ()
+  ///
+  /// out of range index is not checked here, egui fails to parse the face
+  fn resource_font_indexed(&self, fonts: &mut FontDefinitions,
+    n: &str, f: &str, t: FontFamily, index: u32, p: bool) {
+    let Ok(b) = self.read_bytes(f, p) else { return; };
+    let fd = FontData{index, ..FontData::from_owned(b)};
+    reg_font_data(fonts, n, fd, t, false);
+  }
+
+  /// load resource font with FontTweak (baseline and size correction)
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - f: &amp;str filename
+  /// - t: FontFamily family (move)
+  /// - tweak: FontTweak
+  /// - p: bool (true: base of the provider false: full path)
+  /// - result: ()
+  ///
+  /// scale (1.0 is as is) shrinks or grows the glyphs of the font,
+  /// y_offset_factor (fraction of the font size, positive moves down)
+  /// lifts an icon font sitting too low relative to text
+  fn resource_font_tweak(&self, fonts: &mut FontDefinitions,
+    n: &str, f: &str, t: FontFamily, tweak: FontTweak, p: bool) {
+    let Ok(b) = self.read_bytes(f, p) else { return; };
+    let fd = FontData::from_owned(b).tweak(tweak);
+    reg_font_data(fonts, n, fd, t, false);
+  }
+
+  /// reg fonts (p is guessed: true when filename does not contain '/')
+  /// - ffs: Vec&lt; (name, filename, family) &gt; (move)
+  /// - result: FontDefinitions
+  #[deprecated(note = "use reg_fonts_with_paths to pass p explicitly")]
+  fn reg_fonts(&self, ffs: Vec<(&str, &str, FontFamily)>) ->
+    FontDefinitions {
+    self.reg_fonts_with_paths(ffs.into_iter().map(|(n, f, t)|
+      (n, f, t, !f.contains('/'))).collect())
+  }
+
+  /// reg fonts then install them into Context (ctx.set_fonts)
+  /// - ctx: &amp;Context
+  /// - ffs: Vec&lt; (name, filename, family, p) &gt; (move, p: bool
+  ///   true: base of the provider false: full path)
+  /// - result: Result () (nothing is installed when try_reg_fonts fails)
+  ///
+  /// use try_reg_fonts to inspect or merge FontDefinitions before installing
+  fn install_fonts(&self, ctx: &Context,
+    ffs: Vec<(&str, &str, FontFamily, bool)>) -> Result<(), ResourceError> {
+    ctx.set_fonts(self.try_reg_fonts(ffs)?);
+    Ok(())
+  }
+
+  /// reg fonts with paths
+  /// - ffs: Vec&lt; (name, filename, family, p) &gt; (move, p: bool
+  ///   true: base of the provider false: full path)
+  /// - result: FontDefinitions
+  fn reg_fonts_with_paths(&self, ffs: Vec<(&str, &str, FontFamily, bool)>)
+    -> FontDefinitions {
+    self.reg_fonts_into(FontDefinitions::default(), ffs)
+  }
+
+  /// reg fonts with paths into the starting FontDefinitions (layered)
+  /// - fonts: FontDefinitions (move, FontDefinitions::empty() etc)
+  /// - ffs: Vec&lt; (name, filename, family, p) &gt; (move, p: bool
+  ///   true: base of the provider false: full path)
+  /// - result: FontDefinitions
+  fn reg_fonts_into(&self, mut fonts: FontDefinitions,
+    ffs: Vec<(&str, &str, FontFamily, bool)>) -> FontDefinitions {
+    for (n, f, t, p) in ffs.into_iter() {
+      self.resource_font(&mut fonts, n, f, t, p);
+    }
+    fonts
+  }
+
+  /// try reg fonts with paths (duplicate names are not clobbered)
+  /// - ffs: Vec&lt; (name, filename, family, p) &gt; (move, p: bool
+  ///   true: base of the provider false: full path)
+  /// - result: Result FontDefinitions (Invalid when a name collides with
+  ///   another entry or a default font, read error is also returned)
+  ///
+  /// starts from FontDefinitions::default() (as reg_fonts_with_paths),
+  /// so the names of the egui default fonts (Hack, Ubuntu-Light,
+  /// NotoEmoji-Regular, emoji-icon-font) are rejected too
+  fn try_reg_fonts(&self, ffs: Vec<(&str, &str, FontFamily, bool)>) ->
+    Result<FontDefinitions, ResourceError> {
+    let mut fonts = FontDefinitions::default();
+    for (n, f, t, p) in ffs.into_iter() {
+      if fonts.font_data.contains_key(n) {
+        return Err(ResourceError::Invalid(
+          format!("duplicate font name: {} ({})", n, f)));
+      }
+      let b = self.read_bytes(f, p)?;
+      reg_font_data(&mut fonts, n, FontData::from_owned(b), t, false);
+    }
+    Ok(fonts)
+  }
+}
+
+/// FetchResponse (status, status text, bytes)
+#[cfg(feature = "web")]
+type FetchResponse = (u16, String, Vec<u8>);
+
+/// FetchState (result, waker)
+#[cfg(feature = "web")]
+type FetchState =
+  (Option<Result<FetchResponse, String>>, Option<std::task::Waker>);
+
+/// Fetch (future completed by ehttp on wasm32, by a ureq thread on native)
+#[cfg(feature = "web")]
+struct Fetch(std::sync::Arc<std::sync::Mutex<FetchState>>);
+
+/// Fetch
+#[cfg(feature = "web")]
+impl Fetch {
+  /// constructor (start fetching)
+  /// - url: &amp;str
+  fn new(url: &str) -> Self {
+    let st: std::sync::Arc<std::sync::Mutex<FetchState>> = Default::default();
+    let s = st.clone();
+    let done = move |r: Result<FetchResponse, String>| {
+      let mut s = s.lock().unwrap();
+      s.0 = Some(r);
+      if let Some(w) = s.1.take() { w.wake(); }
+    };
+    #[cfg(target_arch = "wasm32")]
+    ehttp::fetch(ehttp::Request::get(url), move |r|
+      done(r.map(|r| (r.status, r.status_text, r.bytes))));
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      let url = url.to_string();
+      std::thread::spawn(move || done(match ureq::get(&url).call() {
+      Ok(r) | Err(ureq::Error::Status(_, r)) => {
+        let (status, text) = (r.status(), r.status_text().to_string());
+        let mut b = vec![];
+        r.into_reader().read_to_end(&mut b).map(|_| (status, text, b))
+          .map_err(|e| e.to_string())
+      },
+      Err(e) => Err(e.to_string())
+      }));
+    }
+    Fetch(st)
+  }
+}
+
+/// Fetch
+#[cfg(feature = "web")]
+impl std::future::Future for Fetch {
+  type Output = Result<FetchResponse, String>;
+
+  /// poll
+  fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) ->
+    std::task::Poll<Self::Output> {
+    let mut s = self.0.lock().unwrap();
+    match s.0.take() {
+    Some(r) => std::task::Poll::Ready(r),
+    None => { s.1 = Some(cx.waker().clone()); std::task::Poll::Pending }
+    }
+  }
+}
+
+/// ResourceOptions (defaults set once on ResourcesBase)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResourceOptions {
+  /// filter used by resizing
+  pub filter: FilterType,
+  /// how rgb of the source relates to alpha
+  pub alpha: AlphaMode,
+  /// allow resizing larger than the source (false: keep at most source size)
+  pub upscale: bool
+}
+
+/// ResourceOptions
+impl Default for ResourceOptions {
+  /// default (Lanczos3, Straight, upscale)
+  fn default() -> Self {
+    ResourceOptions{filter: FilterType::Lanczos3, alpha: AlphaMode::Straight,
+      upscale: true}
+  }
+}
+
+/// ResourceBase
+///
+/// plain paths only (no interior mutability), so it is Clone + Send + Sync
+/// and a clone can be moved into std::thread::spawn for background loading
+#[derive(Clone, Debug)]
+pub struct ResourcesBase {
+  /// base path
+  pub basepath: PathBuf,
+  /// fallback search paths (tried in order after basepath)
+  pub searchpaths: Vec<PathBuf>,
+  /// options used by *_with_options
+  pub options: ResourceOptions
+}
+
+/// ResourcesBase
+impl ResourcesBase {
+  /// constructor
+  /// - basepath: PathBuf base path (move)
+  pub fn new(basepath: PathBuf) -> Self {
+    ResourcesBase{basepath, searchpaths: vec![],
+      options: ResourceOptions::default()}
+  }
+
+  /// add fallback search path (builder)
+  /// - searchpath: PathBuf (move)
+  /// - result: Self
+  pub fn with_searchpath(mut self, searchpath: PathBuf) -> Self {
+    self.searchpaths.push(searchpath);
+    self
+  }
+
+  /// set options (builder)
+  /// - options: ResourceOptions
+  /// - result: Self
+  pub fn with_options(mut self, options: ResourceOptions) -> Self {
+    self.options = options;
+    self
+  }
+
+  /// load resource img with self.options.alpha
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.basepath false: full path)
+  /// - result: ColorImage (ColorImage::example() when failed)
+  pub fn resource_img_with_options(&self, f: &str, p: bool) -> ColorImage {
+    let Ok(b) = self.read_bytes_at(f, p.into()) else {
+      return ColorImage::example();
+    };
+    let Ok(img) = load_from_memory(&b) else { return ColorImage::example(); };
+    color_image_from_dynamic_image_with(img, self.options.alpha)
+  }
+
+  /// load resource img resized with self.options
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.basepath false: full path)
+  /// - wh: [usize; 2] (to be resized, 0 is clamped to 1,
+  ///   shrunk keeping aspect to fit the source when upscale is false)
+  /// - result: ColorImage (ColorImage::example() when failed)
+  pub fn resource_img_resized_with_options(&self, f: &str, p: bool,
+    wh: [usize; 2]) -> ColorImage {
+    let Ok(b) = self.read_bytes_at(f, p.into()) else {
+      return ColorImage::example();
+    };
+    let Ok(img) = load_from_memory(&b) else { return ColorImage::example(); };
+    let Ok((mut w, mut h)) = validate_dims([wh[0].max(1), wh[1].max(1)]) else {
+      return ColorImage::example();
+    };
+    if !self.options.upscale && (w > img.width() || h > img.height()) {
+      let r = (img.width() as f64 / w as f64)
+        .min(img.height() as f64 / h as f64);
+      w = ((w as f64 * r) as u32).max(1);
+      h = ((h as f64 * r) as u32).max(1);
+    }
+    let img = img.resize_to_fill(w, h, self.options.filter);
+    color_image_from_dynamic_image_with(img, self.options.alpha)
+  }
+
+  /// reg fonts dir (.ttf .otf in self.basepath.join(dir), name is file stem)
+  /// - dir: &amp;str sub directory
+  /// - family: FontFamily (move)
+  /// - result: Result FontDefinitions (family starts with sorted names)
+  pub fn reg_fonts_dir(&self, dir: &str, family: FontFamily) ->
+    Result<FontDefinitions, ResourceError> {
+    let d = self.basepath.join(dir);
+    let rd = fs::read_dir(&d).map_err(|e| ResourceError::from_io(e, &d))?;
+    let mut paths = rd.filter_map(|e| e.ok().map(|e| e.path()))
+      .filter(|p| p.is_file() && p.extension().and_then(|x| x.to_str())
+        .is_some_and(|x| ["ttf", "otf"].contains(&&*x.to_lowercase())))
+      .collect::<Vec<_>>();
+    paths.sort();
+    let mut fonts = FontDefinitions::default();
+    for p in paths.iter().rev() { // reversed because each is inserted at 0
+      let Some(n) = p.file_stem().and_then(|n| n.to_str()) else { continue; };
+      let b = fs::read(p)?;
+      reg_font_data(&mut fonts, n, FontData::from_owned(b), family.clone(),
+        false);
+    }
+    Ok(fonts)
+  }
+
+  /// fetch bytes (http, also works on wasm32 without filesystem)
+  /// - url: &amp;str
+  /// - result: Result Vec u8
+  #[cfg(feature = "web")]
+  pub async fn fetch_bytes(&self, url: &str) ->
+    Result<Vec<u8>, ResourceError> {
+    let other = std::io::Error::other;
+    let (status, text, bytes) = Fetch::new(url).await
+      .map_err(|e| ResourceError::Io(other(e)))?;
+    match status {
+    404 => Err(ResourceError::NotFound(PathBuf::from(url))),
+    200..=299 => Ok(bytes),
+    _ => Err(ResourceError::Io(other(format!("fetch {} {} {}",
+      url, status, text))))
+    }
+  }
+
+  /// fetch img (http, also works on wasm32 without filesystem)
+  /// - url: &amp;str
+  /// - result: Result ColorImage
+  #[cfg(feature = "web")]
+  pub async fn fetch_img(&self, url: &str) ->
+    Result<ColorImage, ResourceError> {
+    let b = self.fetch_bytes(url).await?;
+    Ok(color_image_from_memory(&b)?)
+  }
+
+  /// full path
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.basepath false: full path)
+  /// - result: PathBuf (the first existing one in basepath and searchpaths,
+  ///   basepath joined when none exists)
+  pub fn full_path(&self, f: &str, p: bool) -> PathBuf {
+    if !p { return PathBuf::from(f); }
+    let bp = self.basepath.join(f);
+    if bp.exists() { return bp; }
+    self.searchpaths.iter().map(|sp| sp.join(f))
+      .find(|sp| sp.exists()).unwrap_or(bp)
+  }
+}
+
+/// ResourceProvider for ResourcesBase
+impl ResourceProvider for ResourcesBase {
+  /// read bytes at Location
+  /// - f: &amp;str filename
+  /// - l: Location (Relative: self.basepath Absolute: full path)
+  /// - result: Result Vec u8
+  fn read_bytes_at(&self, f: &str, l: Location) ->
+    Result<Vec<u8>, ResourceError> {
+    let mut buf = vec![];
+    self.read_bytes_into(f, l.into(), &mut buf)?;
+    Ok(buf)
+  }
+
+  /// resource exists (without reading, searchpaths are also tried)
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.basepath false: full path)
+  /// - result: bool
+  fn exists(&self, f: &str, p: bool) -> bool {
+    self.full_path(f, p).exists()
+  }
+
+  /// modification time of the file
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.basepath false: full path)
+  /// - result: Result SystemTime
+  fn modified(&self, f: &str, p: bool) -> Result<SystemTime, ResourceError> {
+    let p = self.full_path(f, p);
+    let m = fs::metadata(&p).map_err(|e| ResourceError::from_io(e, &p))?;
+    Ok(m.modified()?)
+  }
+
+  /// list resource files under subdir of basepath and searchpaths recursively
+  /// - subdir: &amp;str sub directory ("" for all)
+  /// - exts: &amp;[&amp;str] (png, .png, etc, case insensitive, empty: any)
+  /// - result: Result Vec PathBuf (sorted and deduped, relative to the root,
+  ///   NotFound when no root has subdir)
+  ///
+  /// symlinked directories are skipped (a cycle would never end)
+  fn list_resources(&self, subdir: &str, exts: &[&str]) ->
+    Result<Vec<PathBuf>, ResourceError> {
+    let mut files = vec![];
+    let mut found = false;
+    for root in std::iter::once(&self.basepath).chain(&self.searchpaths) {
+      let mut dirs = vec![root.join(subdir)];
+      if !dirs[0].is_dir() { continue; }
+      found = true;
+      while let Some(d) = dirs.pop() {
+        let rd = fs::read_dir(&d).map_err(|e| ResourceError::from_io(e, &d))?;
+        for e in rd.filter_map(|e| e.ok()) {
+          let p = e.path();
+          if e.file_type().is_ok_and(|t| t.is_dir()) { dirs.push(p); continue; }
+          if p.is_dir() { continue; } // symlink to a directory
+          if !p.file_name().and_then(|n| n.to_str())
+            .is_some_and(|n| has_ext(n, exts)) { continue; }
+          let r = p.strip_prefix(root).map(Path::to_path_buf);
+          files.push(r.unwrap_or(p));
+        }
+      }
+    }
+    if !found {
+      return Err(ResourceError::NotFound(self.basepath.join(subdir)));
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+  }
+
+  /// load resource img with the modification time (open and stat once)
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.basepath false: full path)
+  /// - result: Result (ColorImage, SystemTime)
+  fn resource_img_with_mtime(&self, f: &str, p: bool) ->
+    Result<(ColorImage, SystemTime), ResourceError> {
+    let p = self.full_path(f, p);
+    let mut fi = fs::File::open(&p).map_err(|e| ResourceError::from_io(e, &p))?;
+    let t = fi.metadata()?.modified()?;
+    let mut b = vec![];
+    fi.read_to_end(&mut b)?;
+    Ok((color_image_from_memory(&b)?, t))
+  }
+
+  /// resource dimensions (read only the header from the file)
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.basepath false: full path)
+  /// - result: Result (width, height)
+  fn resource_dimensions(&self, f: &str, p: bool) ->
+    Result<(u32, u32), ResourceError> {
+    let p = self.full_path(f, p);
+    let r = image::io::Reader::open(&p)
+      .map_err(|e| ResourceError::from_io(e, &p))?.with_guessed_format()?;
+    Ok(r.into_dimensions()?)
+  }
+
+  /// read bytes into the buffer (reuse the allocation)
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.basepath false: full path)
+  /// - buf: &amp;mut Vec&lt;u8&gt; (cleared then filled)
+  /// - result: Result ()
+  fn read_bytes_into(&self, f: &str, p: bool, buf: &mut Vec<u8>) ->
+    Result<(), ResourceError> {
+    let p = self.full_path(f, p);
+    let mut fi = fs::File::open(&p).map_err(|e| ResourceError::from_io(e, &p))?;
+    let metadata = fi.metadata()?;
+    buf.clear();
+    buf.reserve(metadata.len() as usize);
+    fi.read_to_end(buf)?; // read() may return before filling buf
+    Ok(())
+  }
+}
+
+/// ZipResources
+#[cfg(feature = "zip")]
+pub struct ZipResources {
+  /// base path (archive file)
+  pub basepath: PathBuf,
+  /// archive (RefCell because ZipArchive::by_name needs &amp;mut)
+  pub archive: std::cell::RefCell<zip::ZipArchive<fs::File>>
+}
+
+/// ZipResources
+#[cfg(feature = "zip")]
+impl ZipResources {
+  /// constructor
+  /// - basepath: PathBuf archive path (move)
+  /// - result: Result ZipResources (NotFound, Io also for a broken archive)
+  pub fn new(basepath: PathBuf) -> Result<Self, ResourceError> {
+    let fi = fs::File::open(&basepath)
+      .map_err(|e| ResourceError::from_io(e, &basepath))?;
+    let archive = zip::ZipArchive::new(fi)
+      .map_err(|e| ResourceError::Io(e.into()))?;
+    Ok(ZipResources{basepath, archive: std::cell::RefCell::new(archive)})
+  }
+}
+
+/// ResourceProvider for ZipResources
+#[cfg(feature = "zip")]
+impl ResourceProvider for ZipResources {
+  /// read bytes at Location
+  /// - f: &amp;str filename (entry name in the archive)
+  /// - l: Location (Relative: entry in self.basepath Absolute: full path)
+  /// - result: Result Vec u8
+  fn read_bytes_at(&self, f: &str, l: Location) ->
+    Result<Vec<u8>, ResourceError> {
+    let mut buf = vec![];
+    self.read_bytes_into(f, l.into(), &mut buf)?;
+    Ok(buf)
+  }
+
+  /// resource exists (entry presence, without decompressing)
+  /// - f: &amp;str filename (entry name in the archive)
+  /// - p: bool (true: entry in self.basepath false: full path)
+  /// - result: bool
+  fn exists(&self, f: &str, p: bool) -> bool {
+    if !p { return Path::new(f).exists(); }
+    self.archive.borrow().file_names().any(|n| n == f)
+  }
+
+  /// list archive entries under subdir
+  /// - subdir: &amp;str sub directory in the archive ("" for all)
+  /// - exts: &amp;[&amp;str] (png, .png, etc, case insensitive, empty: any)
+  /// - result: Result Vec PathBuf (sorted entry names)
+  fn list_resources(&self, subdir: &str, exts: &[&str]) ->
+    Result<Vec<PathBuf>, ResourceError> {
+    let mut files = self.archive.borrow().file_names()
+      .filter(|n| listed_entry(n, subdir, exts)).map(PathBuf::from)
+      .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+  }
+
+  /// modification time (of the archive file for entries)
+  /// - f: &amp;str filename (entry name in the archive)
+  /// - p: bool (true: entry in self.basepath false: full path)
+  /// - result: Result SystemTime
+  fn modified(&self, f: &str, p: bool) -> Result<SystemTime, ResourceError> {
+    let path = if p { &self.basepath } else { Path::new(f) };
+    if p && !self.exists(f, p) {
+      return Err(ResourceError::NotFound(f.into()));
+    }
+    let m = fs::metadata(path).map_err(|e| ResourceError::from_io(e, path))?;
+    Ok(m.modified()?)
+  }
+
+  /// read bytes into the buffer (reuse the allocation)
+  /// - f: &amp;str filename (entry name in the archive)
+  /// - p: bool (true: entry in self.basepath false: full path)
+  /// - buf: &amp;mut Vec&lt;u8&gt; (cleared then filled)
+  /// - result: Result ()
+  fn read_bytes_into(&self, f: &str, p: bool, buf: &mut Vec<u8>) ->
+    Result<(), ResourceError> {
+    buf.clear();
+    if !p {
+      let mut fi = fs::File::open(f)
+        .map_err(|e| ResourceError::from_io(e, Path::new(f)))?;
+      fi.read_to_end(buf)?;
+      return Ok(());
+    }
+    let mut archive = self.archive.borrow_mut();
+    let mut fi = archive.by_name(f).map_err(|e| match e {
+    zip::result::ZipError::FileNotFound => ResourceError::NotFound(f.into()),
+    e => ResourceError::Io(e.into())
+    })?;
+    buf.reserve(fi.size() as usize);
+    fi.read_to_end(buf)?;
+    Ok(())
+  }
+
+  /// reg fonts (every filename is an entry, even containing '/')
+  /// - ffs: Vec&lt; (name, filename, family) &gt; (move)
+  /// - result: FontDefinitions
+  fn reg_fonts(&self, ffs: Vec<(&str, &str, FontFamily)>) ->
+    FontDefinitions {
+    self.reg_fonts_with_paths(ffs.into_iter().map(|(n, f, t)|
+      (n, f, t, true)).collect())
+  }
+}
+
+/// EmbeddedResources (assets compiled into the executable by rust-embed)
+#[cfg(feature = "rust-embed")]
+pub struct EmbeddedResources<E: rust_embed::RustEmbed> {
+  /// marker of the RustEmbed type
+  embed: std::marker::PhantomData<E>
+}
+
+/// EmbeddedResources
+#[cfg(feature = "rust-embed")]
+impl<E: rust_embed::RustEmbed> EmbeddedResources<E> {
+  /// constructor
+  /// - result: EmbeddedResources (EmbeddedResources::&lt;Assets&gt;::new())
+  pub fn new() -> Self {
+    EmbeddedResources{embed: std::marker::PhantomData}
+  }
+}
+
+/// Default for EmbeddedResources
+#[cfg(feature = "rust-embed")]
+impl<E: rust_embed::RustEmbed> Default for EmbeddedResources<E> {
+  /// default
+  fn default() -> Self { Self::new() }
+}
+
+/// ResourceProvider for EmbeddedResources
+#[cfg(feature = "rust-embed")]
+impl<E: rust_embed::RustEmbed> ResourceProvider for EmbeddedResources<E> {
+  /// read bytes at Location
+  /// - f: &amp;str filename (relative to the folder of RustEmbed)
+  /// - l: Location (Relative: embedded file Absolute: full path)
+  /// - result: Result Vec u8
+  fn read_bytes_at(&self, f: &str, l: Location) ->
+    Result<Vec<u8>, ResourceError> {
+    if l == Location::Absolute {
+      return fs::read(f).map_err(|e| ResourceError::from_io(e, Path::new(f)));
+    }
+    let Some(ef) = E::get(f) else {
+      return Err(ResourceError::NotFound(f.into()));
+    };
+    Ok(ef.data.into_owned())
+  }
+
+  /// resource exists (embedded file presence)
+  /// - f: &amp;str filename (relative to the folder of RustEmbed)
+  /// - p: bool (true: embedded file false: full path)
+  /// - result: bool
+  fn exists(&self, f: &str, p: bool) -> bool {
+    if !p { return Path::new(f).exists(); }
+    E::get(f).is_some()
+  }
+
+  /// list embedded files under subdir
+  /// - subdir: &amp;str sub directory of the RustEmbed folder ("" for all)
+  /// - exts: &amp;[&amp;str] (png, .png, etc, case insensitive, empty: any)
+  /// - result: Result Vec PathBuf (sorted)
+  fn list_resources(&self, subdir: &str, exts: &[&str]) ->
+    Result<Vec<PathBuf>, ResourceError> {
+    let mut files = E::iter().filter(|n| listed_entry(n, subdir, exts))
+      .map(|n| PathBuf::from(&*n)).collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+  }
+}
+
+/// TextureCache
+pub struct TextureCache<T: ResourceProvider = ResourcesBase> {
+  /// resources provider to load from
+  pub base: T,
+  /// decoded images keyed by filename
+  pub imgs: HashMap<String, ColorImage>,
+  /// max total bytes of imgs (usize::MAX: unbounded)
+  pub max_bytes: usize,
+  /// last access tick keyed by filename (for LRU eviction)
+  ticks: HashMap<String, u64>,
+  /// filename keyed by last access tick (oldest first)
+  order: std::collections::BTreeMap<u64, String>,
+  /// total bytes of imgs inserted by cached_img
+  total_bytes: usize,
+  /// access counter
+  tick: u64
+}
+
+/// TextureCache
+impl<T: ResourceProvider> TextureCache<T> {
+  /// constructor (unbounded)
+  /// - base: T ResourceProvider (ResourcesBase, etc) (move)
+  pub fn new(base: T) -> Self {
+    TextureCache::with_capacity(base, usize::MAX)
+  }
+
+  /// constructor bounded by total bytes (least recently used is evicted)
+  /// - base: T ResourceProvider (ResourcesBase, etc) (move)
+  /// - max_bytes: usize (4 bytes per pixel)
+  pub fn with_capacity(base: T, max_bytes: usize) -> Self {
+    TextureCache{base, imgs: HashMap::new(), max_bytes,
+      ticks: HashMap::new(), order: Default::default(), total_bytes: 0,
+      tick: 0}
+  }
+
+  /// total bytes of cached imgs
+  /// - result: usize (4 bytes per pixel, kept as a running total, so imgs
+  ///   inserted into self.imgs directly are not counted)
+  pub fn bytes(&self) -> usize {
+    self.total_bytes
+  }
+
+  /// cached img (decode at the first access, then return the stored copy)
+  /// - f: &amp;str filename (cache key)
+  /// - p: bool (true: self.base.basepath false: full path)
+  /// - result: &amp;ColorImage
+  pub fn cached_img(&mut self, f: &str, p: bool) -> &ColorImage {
+    self.tick += 1;
+    if let Some(t) = self.ticks.insert(f.to_string(), self.tick) {
+      self.order.remove(&t);
+    }
+    self.order.insert(self.tick, f.to_string());
+    if !self.imgs.contains_key(f) {
+      let img = self.base.resource_img_at(f, p.into());
+      self.total_bytes += img.pixels.len() * 4;
+      self.imgs.insert(f.to_string(), img);
+      self.evict(f);
+    }
+    &self.imgs[f]
+  }
+
+  /// evict least recently used imgs while over max_bytes
+  /// - keep: &amp;str filename (never evicted, even if it alone is over)
+  /// - result: ()
+  fn evict(&mut self, keep: &str) {
+    while self.total_bytes > self.max_bytes {
+      let Some(k) = self.order.values().find(|k| *k != keep).cloned() else {
+        break;
+      };
+      self.invalidate(&k);
+    }
+  }
+
+  /// invalidate (reload at the next access)
+  /// - f: &amp;str filename (cache key)
+  /// - result: ()
+  pub fn invalidate(&mut self, f: &str) {
+    if let Some(img) = self.imgs.remove(f) {
+      self.total_bytes = self.total_bytes.saturating_sub(img.pixels.len() * 4);
+    }
+    if let Some(t) = self.ticks.remove(f) { self.order.remove(&t); }
+  }
+
+  /// clear all cached imgs
+  /// - result: ()
+  pub fn clear(&mut self) {
+    self.imgs.clear();
+    self.ticks.clear();
+    self.order.clear();
+    self.total_bytes = 0;
+  }
+}
+
+/// WatchedResources
+pub struct WatchedResources {
+  /// resources base to load from
+  pub base: ResourcesBase,
+  /// modification times keyed by filename
+  pub mtimes: HashMap<String, SystemTime>
+}
+
+/// WatchedResources
+impl WatchedResources {
+  /// constructor
+  /// - base: ResourcesBase (move)
+  pub fn new(base: ResourcesBase) -> Self {
+    WatchedResources{base, mtimes: HashMap::new()}
+  }
+
+  /// check the modification time (recorded by the caller after reloading)
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.base.basepath false: full path)
+  /// - result: Option SystemTime (Some: not loaded yet or modified,
+  ///   None: not modified or deleted)
+  fn changed(&self, f: &str, p: bool) -> Option<SystemTime> {
+    let path = self.base.full_path(f, p);
+    let t = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    if self.mtimes.get(f).is_some_and(|&o| t <= o) { return None; }
+    Some(t)
+  }
+
+  /// reload img if changed (or not loaded yet)
+  /// - f: &amp;str filename
+  /// - p: bool (true: self.base.basepath false: full path)
+  /// - result: Option ColorImage (None: not changed, deleted or failed to
+  ///   decode, a failed one is retried at the next call)
+  pub fn reload_if_changed(&mut self, f: &str, p: bool) -> Option<ColorImage> {
+    let t = self.changed(f, p)?;
+    let img = self.base.try_resource_img(f, p).ok()?;
+    self.mtimes.insert(f.to_string(), t);
+    Some(img)
+  }
+
+  /// reload font if changed (or not loaded yet)
+  /// - fonts: &amp;mut FontDefinitions
+  /// - n: &amp;str name
+  /// - f: &amp;str filename
+  /// - t: FontFamily family (move)
+  /// - p: bool (true: self.base.basepath false: full path)
+  /// - result: bool (true: fonts is updated then call ctx.set_fonts)
+  pub fn reload_font_if_changed(&mut self, fonts: &mut FontDefinitions,
+    n: &str, f: &str, t: FontFamily, p: bool) -> bool {
+    let Some(mt) = self.changed(f, p) else { return false; };
+    let Ok(b) = self.base.read_bytes_at(f, p.into()) else { return false; };
+    self.mtimes.insert(f.to_string(), mt);
+    reg_font_data(fonts, n, FontData::from_owned(b), t, false);
+    true
+  }
+}
+
+/// tests
+#[cfg(test)]
+#[allow(deprecated)] // the bool shims are still tested
+mod tests {
+  use super::*;
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_resources() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let flat = vec![
+[255, 0, 0, 255], [255, 0, 0, 255], [0, 255, 0, 255], [0, 255, 0, 255],
+[255, 0, 0, 255], [255, 0, 0, 255], [0, 255, 0, 255], [0, 255, 0, 255],
+[0, 0, 255, 255], [0, 0, 255, 255], [255, 255, 0, 255], [255, 255, 0, 255],
+[0, 0, 255, 255], [0, 0, 255, 255], [255, 255, 0, 255], [255, 255, 0, 255]
+    ].into_iter().map(|c|
+      Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3]) // unmultiplied
+    ).collect::<Vec<_>>();
+    let im = bp.resource_img("_4c_4x4.png", true);
+    assert_eq!(im.size, [4, 4]);
+    assert_eq!(im.pixels.len(), 16);
+    assert_eq!(im.pixels, flat);
+
+    let resized = vec![
+      [255, 0, 0, 255], [0, 255, 0, 255],
+      [0, 0, 255, 255], [255, 255, 0, 255]
+    ].into_iter().map(|c|
+      Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3]) // unmultiplied
+    ).collect::<Vec<_>>();
+    let img = resized_copy_from([2, 2], &im, FilterType::Nearest);
+    assert_eq!(img.size, [2, 2]);
+    assert_eq!(img.pixels.len(), 4);
+    assert_eq!(img.pixels, resized);
+  }
+
+  /// owned conversion equals the borrowed one
+  #[test]
+  fn test_dynamic_image_from_owned() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let img = bp.resource_img("_4c_4x4.png", true);
+    assert_eq!(dynamic_image_from_owned(img.clone()), dynamic_image_from(&img));
+  }
+
+  /// large file (over 128 KiB) must be read entirely
+  #[test]
+  fn test_read_bytes_large() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let f = "FiraSans-Regular.ttf";
+    let b = bp.read_bytes(f, true).unwrap();
+    assert!(b.len() > 128 * 1024);
+    assert_eq!(b, fs::read(bp.basepath.join(f)).unwrap());
+  }
+
+  /// try_resource_img must propagate errors
+  #[test]
+  fn test_try_resource_img() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    assert!(bp.try_resource_img("_not_exist_.png", true).is_err());
+    assert!(bp.try_resource_img("FiraSans-Regular.ttf", true).is_err());
+    assert_eq!(bp.try_resource_img("_4c_4x4.png", true).unwrap().size, [4, 4]);
+  }
+
+  /// resized_contain_from must keep aspect and pad
+  #[test]
+  fn test_resized_contain_from() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let im = bp.resource_img("_4c_4x4.png", true);
+    let pad = Color32::TRANSPARENT;
+    let img = resized_contain_from([4, 2], &im, FilterType::Nearest, pad);
+    assert_eq!(img.size, [4, 2]);
+    assert_eq!(img.pixels[0], pad);
+    assert_eq!(img.pixels[3], pad);
+    assert_eq!(img.pixels[1], Color32::from_rgb(255, 0, 0));
+    assert_eq!(img.pixels[6], Color32::from_rgb(255, 255, 0));
+    let img = resized_contain_from([2, 4], &im, FilterType::Nearest, pad);
+    assert_eq!(img.size, [2, 4]);
+    assert_eq!(img.pixels[0], pad);
+    assert_eq!(img.pixels[2], Color32::from_rgb(255, 0, 0));
+    let img = resized_contain_from([0, 4], &im, FilterType::Nearest, pad);
+    assert_eq!(img.pixels.len(), 0);
+    let empty = ColorImage::new([0, 0], pad);
+    let img = resized_contain_from([3, 3], &empty, FilterType::Nearest, pad);
+    assert_eq!(img.pixels, vec![pad; 9]);
+    let src = ColorImage::new([2, 2],
+      Color32::from_rgba_unmultiplied(128, 128, 128, 128));
+    let img = resized_contain_from([2, 2], &src, FilterType::Triangle, pad);
+    assert_eq!(img.pixels, src.pixels); // translucent identity
+  }
+
+  /// resource_icon_sized must build IconData at the requested size
+  #[test]
+  fn test_resource_icon_sized() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let ico = bp.resource_icon_sized("_4c_4x4.png", true, [2, 2],
+      FilterType::Nearest).unwrap();
+    assert_eq!((ico.width, ico.height), (2, 2));
+    assert_eq!(ico.rgba, vec![
+      255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 0, 255]);
+  }
+
+  /// ZipResources must read entries through the archive
+  #[cfg(feature = "zip")]
+  #[test]
+  fn test_zip_resources() {
+    let zr = ZipResources::new(PathBuf::from("./resources/_resources.zip"))
+      .unwrap();
+    let im = zr.resource_img("_4c_4x4.png", true);
+    assert_eq!(im.size, [4, 4]);
+    assert_eq!(im.pixels[0], Color32::from_rgb(255, 0, 0));
+    assert!(zr.read_bytes("_not_exist_.png", true).is_err());
+    assert!(zr.resource_icon("_not_exist_.png", true).is_none());
+    assert_eq!(zr.read_bytes("fonts/FiraSans-Regular.ttf", true).unwrap(),
+      fs::read("./resources/FiraSans-Regular.ttf").unwrap());
+    assert!(matches!(ZipResources::new(PathBuf::from("./_not_exist_.zip")),
+      Err(ResourceError::NotFound(_))));
+    let png = PathBuf::from("./resources/_4c_4x4.png");
+    assert!(matches!(ZipResources::new(png),
+      Err(ResourceError::Io(_)))); // not an archive
+  }
+
+  /// list zip entries by subdir and extension
+  #[cfg(feature = "zip")]
+  #[test]
+  fn test_zip_list_resources() {
+    let zr = ZipResources::new(PathBuf::from("./resources/_resources.zip"))
+      .unwrap();
+    assert_eq!(zr.list_resources("", &["PNG"]).unwrap(),
+      vec![PathBuf::from("_4c_4x4.png")]);
+    assert_eq!(zr.list_resources("fonts/", &["ttf"]).unwrap(),
+      vec![PathBuf::from("fonts/FiraSans-Regular.ttf")]);
+    assert_eq!(zr.list_resources("", &[]).unwrap().len(), 2);
+    assert!(zr.list_resources("font", &[]).unwrap().is_empty());
+  }
+
+  /// decode from memory without ResourcesBase
+  #[test]
+  fn test_from_memory() {
+    let b = include_bytes!("../resources/_4c_4x4.png");
+    let im = color_image_from_memory(b).unwrap();
+    assert_eq!(im.size, [4, 4]);
+    let ico = icon_from_memory(b).unwrap();
+    assert_eq!((ico.width, ico.height), (4, 4));
+    assert!(color_image_from_memory(&b[..8]).is_err());
+    let im = color_image_from_reader(Cursor::new(&b[..])).unwrap();
+    assert_eq!(im.size, [4, 4]);
+    assert!(color_image_from_reader(Cursor::new(&b[..8])).is_err());
+    assert!(icon_from_memory(&[]).is_none());
+  }
+
+  /// resource_font inserts at the front, resource_font_append at the back
+  #[test]
+  fn test_resource_font_append() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let mut fonts = FontDefinitions::default();
+    let f = "FiraSans-Regular.ttf";
+    bp.resource_font(&mut fonts, "front", f, FontFamily::Proportional, true);
+    bp.resource_font_append(&mut fonts, "back", f,
+      FontFamily::Proportional, true);
+    let family = &fonts.families[&FontFamily::Proportional];
+    assert_eq!(family.first().unwrap(), "front");
+    assert_eq!(family.last().unwrap(), "back");
+    assert!(fonts.font_data.contains_key("front"));
+    assert!(fonts.font_data.contains_key("back"));
+  }
+
+  /// tint and grayscale keep alpha
+  #[test]
+  fn test_tint_grayscale() {
+    let src = ColorImage{size: [2, 1], pixels: vec![
+      Color32::from_rgb(255, 255, 255),
+      Color32::from_rgba_premultiplied(0, 100, 0, 128)]};
+    let img = tint_color_image(&src, Color32::from_rgb(255, 0, 128));
+    assert_eq!(img.pixels[0], Color32::from_rgb(255, 0, 128));
+    assert_eq!(img.pixels[1], Color32::from_rgba_premultiplied(0, 0, 0, 128));
+    let img = grayscale_color_image(&src);
+    assert_eq!(img.pixels[0], Color32::from_rgb(255, 255, 255));
+    assert_eq!(img.pixels[1],
+      Color32::from_rgba_premultiplied(59, 59, 59, 128));
+  }
+
+  /// flip and rotate non square image
+  #[test]
+  fn test_flip_rotate() {
+    let px = (0..6u8).map(Color32::from_gray).collect::<Vec<_>>();
+    let src = ColorImage{size: [3, 2], pixels: px.clone()}; // 0 1 2 / 3 4 5
+    let order = |img: ColorImage| img.pixels.iter().map(|c|
+      px.iter().position(|p| p == c).unwrap()).collect::<Vec<_>>();
+    assert_eq!(order(flip_horizontal(&src)), vec![2, 1, 0, 5, 4, 3]);
+    assert_eq!(order(flip_vertical(&src)), vec![3, 4, 5, 0, 1, 2]);
+    assert_eq!(order(rotate180(&src)), vec![5, 4, 3, 2, 1, 0]);
+    assert_eq!(rotate90(&src).size, [2, 3]);
+    assert_eq!(order(rotate90(&src)), vec![3, 0, 4, 1, 5, 2]);
+    assert_eq!(rotate270(&src).size, [2, 3]);
+    assert_eq!(order(rotate270(&src)), vec![2, 5, 1, 4, 0, 3]);
+    assert_eq!(rotate270(&rotate90(&src)).pixels, src.pixels);
+  }
+
+  /// crop the middle and clamp out of bounds
+  #[test]
+  fn test_crop_color_image() {
+    let px = (0..16u8).map(Color32::from_gray).collect::<Vec<_>>();
+    let src = ColorImage{size: [4, 4], pixels: px.clone()};
+    let img = crop_color_image(&src, 1, 1, 2, 2);
+    assert_eq!(img.size, [2, 2]);
+    assert_eq!(img.pixels, vec![px[5], px[6], px[9], px[10]]);
+    let img = crop_color_image(&src, 3, 2, 4, 4);
+    assert_eq!(img.size, [1, 2]);
+    assert_eq!(img.pixels, vec![px[11], px[15]]);
+    let img = crop_color_image(&src, 5, 0, 2, 2);
+    assert_eq!(img.size, [0, 2]);
+    assert!(img.pixels.is_empty());
+  }
+
+  /// slice atlas in row-major order
+  #[test]
+  fn test_slice_atlas() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let im = bp.resource_img("_4c_4x4.png", true);
+    let tiles = slice_atlas(&im, 2, 2).unwrap();
+    assert_eq!(tiles.len(), 4);
+    let c = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 0]];
+    for (t, c) in tiles.iter().zip(c.iter()) {
+      assert_eq!(t.size, [2, 2]);
+      assert_eq!(t.pixels, vec![Color32::from_rgb(c[0], c[1], c[2]); 4]);
+    }
+    assert_eq!(slice_atlas(&im, 4, 1).unwrap()[3].size, [1, 4]);
+    assert!(slice_atlas(&im, 3, 2).is_err());
+    assert!(slice_atlas(&im, 0, 2).is_err());
+  }
+
+  /// frames with delay
+  #[test]
+  fn test_resource_frames() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let frames = bp.resource_frames("_2f_2x2.gif", true).unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].0.size, [2, 2]);
+    assert_eq!(frames[0].0.pixels[0], Color32::from_rgb(255, 0, 0));
+    assert_eq!(frames[0].1, Duration::from_millis(100));
+    assert_eq!(frames[1].0.pixels[3], Color32::from_rgb(0, 0, 255));
+    assert_eq!(frames[1].1, Duration::from_millis(200));
+    let frames = bp.resource_frames("_4c_4x4.png", true).unwrap();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].0.size, [4, 4]);
+    assert_eq!(frames[0].1, Duration::ZERO);
+    assert!(bp.resource_frames("_not_exist_.gif", true).is_err());
+  }
+
+  /// reload only when the modification time advanced
+  #[test]
+  fn test_watched_resources() {
+    let dir = std::env::temp_dir().join("egui_resources_test_watched");
+    fs::create_dir_all(&dir).unwrap();
+    let f = "_4c_4x4.png";
+    fs::copy(PathBuf::from("./resources").join(f), dir.join(f)).unwrap();
+    let mut wr = WatchedResources::new(ResourcesBase::new(dir.clone()));
+    assert_eq!(wr.reload_if_changed(f, true).unwrap().size, [4, 4]);
+    assert!(wr.reload_if_changed(f, true).is_none());
+    let t = wr.mtimes[f] + Duration::from_secs(10);
+    fs::File::options().write(true).open(dir.join(f)).unwrap()
+      .set_modified(t).unwrap();
+    assert_eq!(wr.reload_if_changed(f, true).unwrap().size, [4, 4]);
+    fs::remove_file(dir.join(f)).unwrap();
+    assert!(wr.reload_if_changed(f, true).is_none());
+  }
+
+  /// a failed decode (half written file) is retried with the same mtime
+  #[test]
+  fn test_watched_resources_retry() {
+    let dir = std::env::temp_dir().join("egui_resources_test_watched_retry");
+    fs::create_dir_all(&dir).unwrap();
+    let f = "_4c_4x4.png";
+    let png = fs::read(PathBuf::from("./resources").join(f)).unwrap();
+    fs::write(dir.join(f), &png[..8]).unwrap(); // half written
+    let t = fs::metadata(dir.join(f)).unwrap().modified().unwrap();
+    let mut wr = WatchedResources::new(ResourcesBase::new(dir.clone()));
+    assert!(wr.reload_if_changed(f, true).is_none());
+    assert!(!wr.mtimes.contains_key(f));
+    fs::write(dir.join(f), &png).unwrap();
+    fs::File::options().write(true).open(dir.join(f)).unwrap()
+      .set_modified(t).unwrap(); // completed within the same mtime
+    assert_eq!(wr.reload_if_changed(f, true).unwrap().size, [4, 4]);
+    assert_eq!(wr.mtimes[f], t);
+  }
+
+  /// straight and premultiplied alpha differ for translucent pixels
+  #[test]
+  fn test_color_image_from_dynamic_image_premultiplied() {
+    let im = || DynamicImage::from(
+      RgbaImage::from_raw(1, 1, vec![255, 255, 255, 128]).unwrap());
+    let s = color_image_from_dynamic_image(im());
+    let m = color_image_from_dynamic_image_premultiplied(im());
+    let c = [255, 255, 255, 128];
+    assert_eq!(m.pixels[0],
+      Color32::from_rgba_premultiplied(c[0], c[1], c[2], c[3]));
+    assert_eq!(s.pixels[0],
+      Color32::from_rgba_unmultiplied(c[0], c[1], c[2], c[3]));
+    assert_ne!(s.pixels[0], m.pixels[0]);
+  }
+
+  /// zero sized ColorImage must not panic
+  #[test]
+  fn test_dynamic_image_from_empty() {
+    let empty = ColorImage::new([0, 0], Color32::TRANSPARENT);
+    let img = dynamic_image_from(&empty);
+    assert_eq!((img.width(), img.height()), (0, 0));
+    let img = dynamic_image_from_owned(empty);
+    assert_eq!((img.width(), img.height()), (0, 0));
+  }
+
+  /// the first existing file in basepath and searchpaths wins
+  #[test]
+  fn test_searchpaths() {
+    let dir = std::env::temp_dir().join("egui_resources_test_searchpaths");
+    let (theme, default) = (dir.join("mytheme"), dir.join("default"));
+    fs::create_dir_all(&theme).unwrap();
+    fs::create_dir_all(&default).unwrap();
+    fs::write(theme.join("a.txt"), b"theme").unwrap();
+    fs::write(default.join("a.txt"), b"default").unwrap();
+    fs::write(default.join("b.txt"), b"default").unwrap();
+    let bp = ResourcesBase::new(theme).with_searchpath(default);
+    assert_eq!(bp.read_bytes("a.txt", true).unwrap(), b"theme");
+    assert_eq!(bp.read_bytes("b.txt", true).unwrap(), b"default");
+    assert!(bp.read_bytes("c.txt", true).is_err());
+  }
+
+  /// data uri accepts base64 image and rejects others
+  #[cfg(feature = "datauri")]
+  #[test]
+  fn test_color_image_from_data_uri() {
+    use base64::Engine;
+    let b = include_bytes!("../resources/_4c_4x4.png");
+    let e = base64::engine::general_purpose::STANDARD.encode(b);
+    let im = color_image_from_data_uri(&format!("data:image/png;base64,{}", e))
+      .unwrap();
+    assert_eq!(im.size, [4, 4]);
+    assert!(color_image_from_data_uri(&format!("data:text/plain;base64,{}", e))
+      .is_err());
+    assert!(color_image_from_data_uri(&format!("data:image/png,{}", e))
+      .is_err());
+    assert!(color_image_from_data_uri("image/png;base64,AAAA").is_err());
+    assert!(color_image_from_data_uri("data:image/png;base64").is_err());
+    assert!(color_image_from_data_uri("data:image/png;base64,!!").is_err());
+  }
+
+  /// save and reload
+  #[test]
+  fn test_save_color_image() {
+    let dir = std::env::temp_dir().join("egui_resources_test_save");
+    fs::create_dir_all(&dir).unwrap();
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let im = bp.resource_img("_4c_4x4.png", true);
+    let bd = ResourcesBase::new(dir.clone());
+    save_color_image(&im, &dir.join("a.png"), ImageFormat::Png).unwrap();
+    let img = bd.try_resource_img("a.png", true).unwrap();
+    assert_eq!(img.size, [4, 4]);
+    assert_eq!(img.pixels, im.pixels);
+    save_color_image(&im, &dir.join("a.jpg"), ImageFormat::Jpeg).unwrap();
+    assert_eq!(bd.try_resource_img("a.jpg", true).unwrap().size, [4, 4]);
+  }
+
+  /// translucent pixels round trip through a saved png unchanged
+  #[test]
+  fn test_save_color_image_translucent() {
+    let dir = std::env::temp_dir().join("egui_resources_test_save_alpha");
+    fs::create_dir_all(&dir).unwrap();
+    let im = ColorImage{size: [2, 1], pixels: vec![
+      Color32::from_rgba_unmultiplied(128, 128, 128, 128),
+      Color32::from_rgba_unmultiplied(255, 64, 0, 32)]};
+    save_color_image(&im, &dir.join("a.png"), ImageFormat::Png).unwrap();
+    let img = ResourcesBase::new(dir).try_resource_img("a.png", true).unwrap();
+    assert_eq!(img.pixels, im.pixels);
+  }
+
+  /// indexed png reloads with at most max_colors
+  #[test]
+  fn test_save_indexed_png() {
+    let dir = std::env::temp_dir().join("egui_resources_test_indexed");
+    fs::create_dir_all(&dir).unwrap();
+    let bd = ResourcesBase::new(dir.clone());
+    let im = ResourcesBase::new(PathBuf::from("./resources"))
+      .resource_img("_4c_4x4.png", true);
+    save_indexed_png(&im, &dir.join("a.png"), 16).unwrap();
+    assert_eq!(bd.try_resource_img("a.png", true).unwrap().pixels, im.pixels);
+    let im = ColorImage{size: [2, 1], pixels: vec![Color32::TRANSPARENT,
+      Color32::from_rgba_unmultiplied(128, 128, 128, 128)]};
+    save_indexed_png(&im, &dir.join("c.png"), 16).unwrap();
+    assert_eq!(bd.try_resource_img("c.png", true).unwrap().pixels, im.pixels);
+    let im = ColorImage{size: [16, 16], pixels: (0..256)
+      .map(|i| Color32::from_rgb(i as u8, (255 - i) as u8, 128)).collect()};
+    save_indexed_png(&im, &dir.join("b.png"), 4).unwrap();
+    let img = bd.try_resource_img("b.png", true).unwrap();
+    assert_eq!(img.size, [16, 16]);
+    let colors = img.pixels.iter().collect::<std::collections::HashSet<_>>();
+    assert!(colors.len() <= 4);
+  }
+
+  /// integer upscale duplicates pixels into blocks
+  #[test]
+  fn test_scale_integer() {
+    let px = (0..4u8).map(Color32::from_gray).collect::<Vec<_>>();
+    let src = ColorImage{size: [2, 2], pixels: px.clone()};
+    let img = scale_integer(&src, 3);
+    assert_eq!(img.size, [6, 6]);
+    assert_eq!(img.pixels[0..6], [px[0], px[0], px[0], px[1], px[1], px[1]]);
+    assert_eq!(img.pixels[6 * 5 + 5], px[3]);
+    assert_eq!(scale_integer(&src, 0).pixels, px);
+  }
+
+  /// embedded font without file IO
+  #[test]
+  fn test_resource_font_static() {
+    let bp = ResourcesBase::new(PathBuf::from("./_not_exist_"));
+    let mut fonts = FontDefinitions::default();
+    bp.resource_font_static(&mut fonts, "fira",
+      include_bytes!("../resources/FiraSans-Regular.ttf"),
+      FontFamily::Monospace);
+    assert!(fonts.font_data.contains_key("fira"));
+    assert_eq!(fonts.families[&FontFamily::Monospace][0], "fira");
+  }
+
+  /// any ResourceProvider gets the decode methods
+  #[test]
+  fn test_resource_provider() {
+    struct Mem(HashMap<String, Vec<u8>>);
+    impl ResourceProvider for Mem {
+      fn read_bytes_at(&self, f: &str, _l: Location) ->
+        Result<Vec<u8>, ResourceError> {
+        self.0.get(f).cloned()
+          .ok_or_else(|| ResourceError::NotFound(f.into()))
+      }
+    }
+    let b = include_bytes!("../resources/_4c_4x4.png").to_vec();
+    let m = Mem(HashMap::from([("a.png".to_string(), b)]));
+    assert_eq!(m.resource_img("a.png", true).size, [4, 4]);
+    assert!(m.try_resource_img("b.png", true).is_err());
+    assert!(m.resource_icon("a.png", true).is_some());
+    let mut tc = TextureCache::new(m);
+    assert_eq!(tc.cached_img("a.png", true).size, [4, 4]);
+  }
+
+  /// forced format
+  #[test]
+  fn test_resource_img_with_format() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let f = "_4c_4x4.png";
+    assert_eq!(bp.resource_img_with_format(f, true, ImageFormat::Png).size,
+      [4, 4]);
+    assert_eq!(bp.resource_img_with_format(f, true, ImageFormat::Bmp).size,
+      ColorImage::example().size);
+  }
+
+  /// source-over blend clipped to base
+  #[test]
+  fn test_overlay_color_image() {
+    let blue = Color32::from_rgb(0, 0, 255);
+    let red = Color32::from_rgba_premultiplied(128, 0, 0, 128);
+    let mut base = ColorImage::new([3, 3], blue);
+    let overlay = ColorImage::new([2, 2], red);
+    overlay_color_image(&mut base, &overlay, 2, 1);
+    let blended = Color32::from_rgba_premultiplied(128, 0, 127, 255);
+    assert_eq!(base.pixels, vec![
+      blue, blue, blue,
+      blue, blue, blended,
+      blue, blue, blended]);
+    overlay_color_image(&mut base, &overlay, 3, 3); // out of base
+    assert_eq!(base.pixels[8], blended);
+  }
+
+  /// pad 2x2 by 1px on each side
+  #[test]
+  fn test_pad_color_image() {
+    let px = (1..5u8).map(Color32::from_gray).collect::<Vec<_>>();
+    let src = ColorImage{size: [2, 2], pixels: px.clone()};
+    let t = Color32::TRANSPARENT;
+    let img = pad_color_image(&src, 1, 1, 1, 1, t);
+    assert_eq!(img.size, [4, 4]);
+    assert_eq!(img.pixels, vec![
+      t, t, t, t,
+      t, px[0], px[1], t,
+      t, px[2], px[3], t,
+      t, t, t, t]);
+    assert_eq!(pad_color_image(&src, 0, 2, 1, 0, t).size, [4, 3]);
+  }
+
+  /// dimensions are validated before resizing
+  #[test]
+  fn test_validate_dims() {
+    assert_eq!(validate_dims([3, 2]).unwrap(), (3, 2));
+    assert!(matches!(validate_dims([0, 2]), Err(ResourceError::Invalid(_))));
+    assert!(validate_dims([usize::MAX, 2]).is_err());
+    assert!(validate_dims([1 << 31, 1 << 31]).is_err()); // w * h * 4
+    let src = ColorImage::new([4, 4], Color32::RED);
+    let ex = ColorImage::example().size;
+    assert_eq!(resized_copy_from([usize::MAX, 1], &src,
+      FilterType::Nearest).size, ex);
+    assert_eq!(resized_copy_from([0, 2], &src, FilterType::Nearest).size,
+      [1, 2]); // 0 is still clamped to 1
+    assert_eq!(thumbnail_from(&src, 0, FilterType::Nearest).size, ex);
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    assert!(bp.resource_icon_sized("_4c_4x4.png", true, [0, 16],
+      FilterType::Nearest).is_none());
+    assert_eq!(bp.resource_icon_set("_4c_4x4.png", true, &[0, 16]).len(), 1);
+  }
+
+  /// try_ resize entry points return Invalid for zero and too large
+  #[test]
+  fn test_try_resize_dims() {
+    let src = ColorImage::new([4, 4], Color32::RED);
+    let f = FilterType::Nearest;
+    let inv = |r: Result<ColorImage, ResourceError>|
+      matches!(r, Err(ResourceError::Invalid(_)));
+    assert_eq!(try_resized_copy_from([2, 3], &src, f).unwrap().size, [2, 3]);
+    assert!(inv(try_resized_copy_from([0, 2], &src, f)));
+    assert!(inv(try_resized_copy_from([usize::MAX, 1], &src, f)));
+    assert!(inv(try_resized_contain_from([0, 2], &src, f, Color32::BLACK)));
+    assert!(inv(try_resized_premultiplied(&src, [2, 0], f)));
+    assert!(inv(try_resize_with_edge_extend(&src, [2, 2], f, usize::MAX)));
+    assert!(inv(try_thumbnail_from(&src, 0, f)));
+    assert!(inv(try_nine_slice(&src, [1; 4], [0, 8], f)));
+    assert_eq!(try_scale_integer(&src, 2).unwrap().size, [8, 8]);
+    assert!(inv(try_scale_integer(&src, 0)));
+    assert!(inv(try_scale_integer(&src, usize::MAX)));
+    let ex = ColorImage::example().size;
+    assert_eq!(scale_integer(&src, usize::MAX).size, ex); // no panic
+    let c = Color32::BLACK;
+    assert_eq!(try_pad_color_image(&src, 1, 1, 1, 1, c).unwrap().size, [6, 6]);
+    assert!(inv(try_pad_color_image(&src, 0, usize::MAX, 0, 0, c)));
+    assert_eq!(pad_color_image(&src, usize::MAX, 0, 0, 0, c).size, ex);
+  }
+
+  /// svg rasterizing size is validated
+  #[cfg(feature = "svg")]
+  #[test]
+  fn test_resource_svg_dims() {
+    let dir = std::env::temp_dir().join("egui_resources_test_svg");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.svg"), concat!(
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="2">"#,
+      r#"<rect width="4" height="2" fill="red"/></svg>"#)).unwrap();
+    let bd = ResourcesBase::new(dir);
+    assert_eq!(bd.resource_svg("a.svg", true, [8, 0]).size, [8, 4]);
+    assert_eq!(bd.resource_svg("a.svg", true, [usize::MAX, 1]).size,
+      ColorImage::example().size);
+  }
+
+  /// thumbnail keeps aspect and never upscales
+  #[test]
+  fn test_thumbnail_from() {
+    let src = ColorImage::new([4, 2], Color32::RED);
+    let img = thumbnail_from(&src, 2, FilterType::Nearest);
+    assert_eq!(img.size, [2, 1]);
+    assert_eq!(img.pixels, vec![Color32::RED; 2]);
+    assert_eq!(thumbnail_from(&src, 8, FilterType::Nearest).size, [4, 2]);
+    assert_eq!(thumbnail_from(&src, 4, FilterType::Nearest).size, [4, 2]);
+  }
+
+  /// read_bytes_into reuses the buffer
+  #[test]
+  fn test_read_bytes_into() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let mut buf = vec![];
+    bp.read_bytes_into("FiraSans-Regular.ttf", true, &mut buf).unwrap();
+    let cap = buf.capacity();
+    bp.read_bytes_into("_4c_4x4.png", true, &mut buf).unwrap();
+    assert_eq!(buf, fs::read("./resources/_4c_4x4.png").unwrap());
+    assert_eq!(buf.capacity(), cap);
+    assert!(bp.read_bytes_into("_not_exist_", true, &mut buf).is_err());
+  }
+
+  /// ResourceError distinguishes not found from decode failure
+  #[test]
+  fn test_resource_error() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    match bp.try_resource_img("_not_exist_.png", true) {
+    Err(ResourceError::NotFound(p)) => assert!(p.ends_with("_not_exist_.png")),
+    _ => panic!("expected NotFound")
+    }
+    match bp.try_resource_img("FiraSans-Regular.ttf", true) {
+    Err(e @ ResourceError::Decode(_)) => assert!(e.source().is_some()),
+    _ => panic!("expected Decode")
+    }
+    let e: Box<dyn Error> = bp.read_bytes("_x_", true).unwrap_err().into();
+    assert!(e.to_string().starts_with("not found: "));
+  }
+
+  /// one IconData per size
+  #[test]
+  fn test_resource_icon_set() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let icons = bp.resource_icon_set("_4c_4x4.png", true, &[2, 4, 8]);
+    let wh = icons.iter().map(|i| (i.width, i.height)).collect::<Vec<_>>();
+    assert_eq!(wh, vec![(2, 2), (4, 4), (8, 8)]);
+    assert!(bp.resource_icon_set("_not_exist_.png", true, &[16]).is_empty());
+  }
+
+  /// register all fonts in a directory
+  #[test]
+  fn test_reg_fonts_dir() {
+    let dir = std::env::temp_dir().join("egui_resources_test_fonts_dir");
+    fs::create_dir_all(dir.join("fonts")).unwrap();
+    let f = "./resources/FiraSans-Regular.ttf";
+    fs::copy(f, dir.join("fonts/b.TTF")).unwrap();
+    fs::copy(f, dir.join("fonts/a.otf")).unwrap();
+    fs::write(dir.join("fonts/readme.txt"), b"not a font").unwrap();
+    let bp = ResourcesBase::new(dir);
+    let fonts = bp.reg_fonts_dir("fonts", FontFamily::Monospace).unwrap();
+    assert!(fonts.font_data.contains_key("a"));
+    assert!(fonts.font_data.contains_key("b"));
+    assert!(!fonts.font_data.contains_key("readme"));
+    assert_eq!(fonts.families[&FontFamily::Monospace][0..2], ["a", "b"]);
+    assert!(matches!(bp.reg_fonts_dir("_x_", FontFamily::Monospace),
+      Err(ResourceError::NotFound(_))));
+  }
+
+  /// zero target size is clamped to 1
+  #[test]
+  fn test_resized_copy_from_zero() {
+    let src = ColorImage::new([4, 4], Color32::RED);
+    for wh in [[0, 4], [4, 0], [0, 0]] {
+      let img = resized_copy_from(wh, &src, FilterType::Nearest);
+      assert_eq!(img.size, [wh[0].max(1), wh[1].max(1)]);
+      assert_eq!(img.pixels.len(), img.size[0] * img.size[1]);
+    }
+  }
+
+  /// decode webp
+  #[cfg(feature = "webp")]
+  #[test]
+  fn test_resource_img_webp() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let im = bp.try_resource_img("_1c_2x2.webp", true).unwrap();
+    assert_eq!(im.size, [2, 2]);
+    assert_eq!(im.pixels, vec![Color32::from_rgb(255, 0, 0); 4]);
+  }
+
+  /// compare within tolerance
+  #[test]
+  fn test_compare_color_images() {
+    let a = ColorImage::new([2, 2], Color32::from_rgb(100, 100, 100));
+    let mut b = a.clone();
+    b.pixels[3] = Color32::from_rgb(100, 103, 98);
+    assert_eq!(max_channel_diff(&a, &b), 3);
+    assert!(compare_color_images(&a, &b, 3));
+    assert!(!compare_color_images(&a, &b, 2));
+    let c = ColorImage::new([4, 1], Color32::from_rgb(100, 100, 100));
+    assert!(!compare_color_images(&a, &c, 255));
+  }
+
+  /// center crop to aspect
+  #[test]
+  fn test_crop_to_aspect() {
+    let px = (0..8u8).map(Color32::from_gray).collect::<Vec<_>>();
+    let src = ColorImage{size: [4, 2], pixels: px.clone()};
+    let img = crop_to_aspect(&src, 1, 1);
+    assert_eq!(img.size, [2, 2]);
+    assert_eq!(img.pixels, vec![px[1], px[2], px[5], px[6]]);
+    assert_eq!(crop_to_aspect(&src, 4, 1).size, [4, 1]);
+    assert_eq!(crop_to_aspect(&src, 16, 9).size, [3, 2]);
+    let big = usize::MAX / 2;
+    assert_eq!(crop_to_aspect(&src, big, big).size, [2, 2]); // no overflow
+    assert_eq!(crop_to_aspect(&src, usize::MAX, 1).size, [4, 0]);
+  }
+
+  /// filter by name
+  #[test]
+  fn test_filter_from_str() {
+    assert_eq!(filter_from_str("Nearest"), Some(FilterType::Nearest));
+    assert_eq!(filter_from_str("CATMULLROM"), Some(FilterType::CatmullRom));
+    assert_eq!(filter_from_str("lanczos3"), Some(FilterType::Lanczos3));
+    assert_eq!(filter_from_str("bicubic"), None);
+  }
+
+  /// dimensions without decoding pixels
+  #[test]
+  fn test_resource_dimensions() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    assert_eq!(bp.resource_dimensions("_4c_4x4.png", true).unwrap(), (4, 4));
+    assert_eq!(bp.resource_dimensions("_2f_2x2.gif", true).unwrap(), (2, 2));
+    assert!(matches!(bp.resource_dimensions("_x_.png", true),
+      Err(ResourceError::NotFound(_))));
+    assert!(bp.resource_dimensions("FiraSans-Regular.ttf", true).is_err());
+  }
+
+  /// capped load rejects by the header dimensions
+  #[test]
+  fn test_resource_img_capped() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let img = bp.resource_img_capped("_4c_4x4.png", true, 16).unwrap();
+    assert_eq!(img.size, [4, 4]);
+    assert!(matches!(bp.resource_img_capped("_4c_4x4.png", true, 15),
+      Err(ResourceError::Invalid(_))));
+    assert!(matches!(bp.resource_img_capped("_x_.png", true, 16),
+      Err(ResourceError::NotFound(_))));
+  }
+
+  /// one font for all families
+  #[test]
+  fn test_resource_font_all() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let mut fonts = FontDefinitions::default();
+    let n = fonts.font_data.len();
+    bp.resource_font_all(&mut fonts, "fira", "FiraSans-Regular.ttf", true);
+    assert_eq!(fonts.font_data.len(), n + 1);
+    assert_eq!(fonts.families[&FontFamily::Proportional][0], "fira");
+    assert_eq!(fonts.families[&FontFamily::Monospace][0], "fira");
+    let (pn, mn) = (fonts.families[&FontFamily::Proportional].len(),
+      fonts.families[&FontFamily::Monospace].len());
+    bp.resource_font_all(&mut fonts, "fira", "FiraSans-Regular.ttf", true);
+    assert_eq!(fonts.families[&FontFamily::Proportional].len(), pn);
+    assert_eq!(fonts.families[&FontFamily::Monospace].len(), mn); // no dup
+  }
+
+  /// blur and sharpen keep size and alpha
+  #[test]
+  fn test_blur_sharpen() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let im = bp.resource_img("_4c_4x4.png", true);
+    let img = blur_color_image(&im, 1.0);
+    assert_eq!(img.size, [4, 4]);
+    assert!(img.pixels.iter().all(|c| c.a() == 255));
+    assert!(max_channel_diff(&im, &img) > 0);
+    let img = sharpen_color_image(&im, 1.0, 0);
+    assert_eq!(img.size, [4, 4]);
+    assert!(img.pixels.iter().all(|c| c.a() == 255));
+    for c in [Color32::from_rgba_unmultiplied(128, 128, 128, 128),
+      Color32::from_rgba_unmultiplied(255, 64, 0, 32)] {
+      let src = ColorImage::new([3, 3], c); // translucent flat region
+      assert_eq!(blur_color_image(&src, 0.1).pixels, src.pixels);
+      assert_eq!(blur_color_image(&src, 2.0).pixels, src.pixels);
+      assert_eq!(sharpen_color_image(&src, 1.0, 0).pixels, src.pixels);
+    }
+    let src = ColorImage{size: [2, 1], pixels: vec![
+      Color32::from_rgba_unmultiplied(128, 128, 128, 128),
+      Color32::from_gray(128)]}; // one straight color, translucent and opaque
+    assert_eq!(blur_color_image(&src, 2.0).pixels, src.pixels);
+  }
+
+  /// recolor keeps transparent pixels transparent
+  #[test]
+  fn test_recolor_opaque() {
+    let src = ColorImage{size: [2, 1], pixels: vec![
+      Color32::TRANSPARENT, Color32::from_rgb(10, 20, 30)]};
+    let img = recolor_opaque(&src, Color32::from_rgb(255, 0, 128));
+    assert_eq!(img.pixels[0], Color32::TRANSPARENT);
+    assert_eq!(img.pixels[1], Color32::from_rgb(255, 0, 128));
+  }
+
+  /// rgba bytes are premultiplied and tightly packed
+  #[test]
+  fn test_rgba_bytes() {
+    let src = ColorImage{size: [2, 1], pixels: vec![
+      Color32::from_rgb(1, 2, 3),
+      Color32::from_rgba_premultiplied(128, 0, 0, 128)]};
+    assert_eq!(rgba_bytes(&src), vec![1, 2, 3, 255, 128, 0, 0, 128]);
+    assert_eq!(rgba_bytes_ref(&src), &rgba_bytes(&src)[..]);
+  }
+
+  /// mipmaps halve down to 1x1
+  #[test]
+  fn test_generate_mipmaps() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let im = bp.resource_img("_4c_4x4.png", true);
+    let mips = generate_mipmaps(&im, FilterType::Triangle);
+    let sizes = mips.iter().map(|m| m.size).collect::<Vec<_>>();
+    assert_eq!(sizes, vec![[4, 4], [2, 2], [1, 1]]);
+    let src = ColorImage::new([5, 1], Color32::WHITE);
+    let sizes = generate_mipmaps(&src, FilterType::Nearest).iter()
+      .map(|m| m.size).collect::<Vec<_>>();
+    assert_eq!(sizes, vec![[5, 1], [3, 1], [2, 1], [1, 1]]);
+    let c = Color32::from_rgba_unmultiplied(128, 128, 128, 128);
+    let src = ColorImage::new([8, 8], c); // flat translucent
+    for m in generate_mipmaps(&src, FilterType::Triangle) {
+      assert!(m.pixels.iter().all(|p| *p == c));
+    }
+  }
+
+  /// ResourcesBase can be cloned into another thread
+  #[test]
+  fn test_resources_base_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ResourcesBase>();
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let b = bp.clone();
+    let sz = std::thread::spawn(move ||
+      b.resource_img("_4c_4x4.png", true).size).join().unwrap();
+    assert_eq!(sz, [4, 4]);
+    assert!(format!("{:?}", bp).contains("resources"));
+  }
+
+  /// spawn_load decodes off thread
+  #[test]
+  fn test_spawn_load() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let h = bp.spawn_load("_4c_4x4.png".to_string(), true);
+    let img = h.join().unwrap().unwrap();
+    assert_eq!(img.pixels, bp.resource_img("_4c_4x4.png", true).pixels);
+    let h = bp.spawn_load("_not_exist_.png".to_string(), true);
+    assert!(matches!(h.join().unwrap(), Err(ResourceError::NotFound(_))));
+  }
+
+  /// resource_img_or returns the fallback when failed
+  #[test]
+  fn test_resource_img_or() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let fb = ColorImage::new([3, 3], Color32::RED);
+    let img = bp.resource_img_or("_not_exist_.png", true, &fb);
+    assert_eq!((img.size, img.pixels), (fb.size, fb.pixels.clone()));
+    assert_eq!(bp.resource_img_or("_4c_4x4.png", true, &fb).size, [4, 4]);
+  }
+
+  /// batch loading keyed by name
+  #[test]
+  fn test_load_img_batch() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let names = ["_4c_4x4.png", "_not_exist_.png"];
+    let imgs = bp.load_img_batch(&names, true);
+    assert_eq!(imgs.len(), 2);
+    assert_eq!(imgs["_4c_4x4.png"].size, [4, 4]);
+    assert_eq!(imgs["_not_exist_.png"].size, ColorImage::example().size);
+    let (imgs, errs) = bp.try_load_img_batch(&names, true);
+    assert_eq!(imgs.len(), 1);
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].0, "_not_exist_.png");
+    assert!(matches!(errs[0].1, ResourceError::NotFound(_)));
+  }
+
+  /// parallel batch loading matches the serial one
+  #[cfg(feature = "rayon")]
+  #[test]
+  fn test_load_img_batch_par() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let names = ["_4c_4x4.png", "_not_exist_.png", "_2f_2x2.gif"];
+    let imgs = bp.load_img_batch_par(&names, true);
+    let seq = bp.load_img_batch(&names, true);
+    assert_eq!(imgs.len(), 3);
+    for n in names { assert_eq!(imgs[n].pixels, seq[n].pixels); }
+  }
+
+  /// checkerboard alternates every cell pixels
+  #[test]
+  fn test_placeholder_checkerboard() {
+    let (a, b) = (Color32::BLACK, Color32::from_rgb(255, 0, 255));
+    let img = placeholder_checkerboard([5, 3], a, b, 2);
+    assert_eq!(img.size, [5, 3]);
+    assert_eq!(img.pixels[0..5], [a, a, b, b, a]);
+    assert_eq!(img.pixels[5..10], [a, a, b, b, a]);
+    assert_eq!(img.pixels[10..15], [b, b, a, a, b]);
+    let img = placeholder_checkerboard([2, 2], a, b, 0);
+    assert_eq!(img.pixels, vec![a, b, b, a]);
+  }
+
+  /// unpremultiply(premultiply(x)) is near x
+  #[test]
+  fn test_premultiply() {
+    let px = |r, g, b, a| Color32::from_rgba_premultiplied(r, g, b, a);
+    let src = ColorImage{size: [4, 1], pixels: vec![
+      px(255, 128, 0, 255), px(200, 100, 50, 128), px(255, 255, 255, 192),
+      px(10, 20, 30, 0)]};
+    let pm = premultiply(&src);
+    assert_eq!(pm.pixels[1], px(100, 50, 25, 128));
+    assert_eq!(pm.pixels[3], px(0, 0, 0, 0));
+    let img = unpremultiply(&pm);
+    assert_eq!(img.pixels[3], px(0, 0, 0, 0)); // rgb is lost when a is 0
+    let src = ColorImage{size: [3, 1], pixels: src.pixels[..3].to_vec()};
+    let img = ColorImage{size: [3, 1], pixels: img.pixels[..3].to_vec()};
+    assert!(compare_color_images(&src, &img, 1));
+  }
+
+  /// tile 2x2 into 5x5 wraps around
+  #[test]
+  fn test_tile_color_image() {
+    let px = [Color32::RED, Color32::GREEN, Color32::BLUE, Color32::WHITE];
+    let src = ColorImage{size: [2, 2], pixels: px.to_vec()};
+    let img = tile_color_image(&src, [5, 5]);
+    assert_eq!(img.size, [5, 5]);
+    assert_eq!(img.pixels[0], px[0]); // (0, 0)
+    assert_eq!(img.pixels[3], px[1]); // (3, 0)
+    assert_eq!(img.pixels[4], px[0]); // (4, 0)
+    assert_eq!(img.pixels[5 * 3 + 2], px[2]); // (2, 3)
+    assert_eq!(img.pixels[5 * 4 + 4], px[0]); // (4, 4)
+    let img = tile_color_image(&src, [1, 2]);
+    assert_eq!(img.pixels, vec![px[0], px[2]]);
+  }
+
+  /// ico frame nearest not smaller, else the largest
+  #[test]
+  fn test_resource_ico_frame() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let f = "_2s_16_32.ico";
+    let img = bp.resource_ico_frame(f, true, 16).unwrap();
+    assert_eq!((img.size, img.pixels[0]), ([16, 16], Color32::RED));
+    let img = bp.resource_ico_frame(f, true, 20).unwrap();
+    assert_eq!((img.size, img.pixels[0]), ([32, 32], Color32::BLUE));
+    assert_eq!(bp.resource_ico_frame(f, true, 8).unwrap().size, [16, 16]);
+    assert_eq!(bp.resource_ico_frame(f, true, 64).unwrap().size, [32, 32]);
+    assert!(matches!(bp.resource_ico_frame("_4c_4x4.png", true, 16),
+      Err(ResourceError::Invalid(_))));
+  }
+
+  /// manifest loads images and registers fonts
+  #[cfg(feature = "manifest")]
+  #[test]
+  fn test_load_manifest() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let mut fonts = FontDefinitions::empty();
+    let imgs = bp.load_manifest("_assets.json", &mut fonts).unwrap();
+    assert_eq!(imgs.len(), 1);
+    assert_eq!(imgs["c4"].size, [4, 4]);
+    assert!(fonts.font_data.contains_key("fira"));
+    assert_eq!(fonts.families[&FontFamily::Monospace], vec!["fira_mono"]);
+    assert!(matches!(bp.load_manifest("_4c_4x4.png", &mut fonts),
+      Err(ResourceError::Invalid(_))));
+  }
+
+  /// size and pixel count survive the conversion to ImageData
+  #[test]
+  fn test_into_image_data() {
+    let src = ColorImage::new([3, 2], Color32::RED);
+    let ImageData::Color(img) = image_data_from(&src) else { panic!() };
+    assert_eq!((img.size, img.pixels.len()), ([3, 2], 6));
+    let ImageData::Color(img) = into_image_data(src) else { panic!() };
+    assert_eq!(img.pixels, vec![Color32::RED; 6]);
+    let bad = ColorImage{size: [3, 2], pixels: vec![Color32::RED; 5]};
+    let ImageData::Color(img) = into_image_data(bad) else { panic!() };
+    assert_eq!(img.size, ColorImage::example().size);
+  }
+
+  /// ImageData round trip, font coverage becomes gray
+  #[test]
+  fn test_color_image_from_image_data() {
+    let src = ColorImage::new([3, 2], Color32::RED);
+    let img = color_image_from_image_data(&image_data_from(&src)).unwrap();
+    assert_eq!((img.size, img.pixels), (src.size, src.pixels));
+    let font = FontImage{size: [2, 1], pixels: vec![0.0, 1.0]};
+    let img = color_image_from_image_data(&ImageData::Font(font)).unwrap();
+    assert_eq!(img.size, [2, 1]);
+    assert_eq!(img.pixels, vec![Color32::TRANSPARENT, Color32::WHITE]);
+  }
+
+  /// composite each BlendMode on overlapping solid colors
+  #[test]
+  fn test_composite() {
+    let d = Color32::from_rgb(200, 100, 0);
+    let s = Color32::from_rgb(100, 100, 100);
+    let cases = [
+      (BlendMode::Normal, Color32::from_rgb(100, 100, 100)),
+      (BlendMode::Multiply, Color32::from_rgb(78, 39, 0)),
+      (BlendMode::Screen, Color32::from_rgb(222, 161, 100)),
+      (BlendMode::Add, Color32::from_rgb(255, 200, 100))];
+    for (mode, expect) in cases {
+      let img = composite(&[
+        (ColorImage::new([2, 1], d), [0, 0], BlendMode::Normal),
+        (ColorImage::new([1, 2], s), [1, 0], mode)]);
+      assert_eq!(img.size, [2, 2]); // bounding box
+      assert_eq!(img.pixels, vec![d, expect, Color32::TRANSPARENT, s]);
+    }
+    let half = Color32::from_rgba_premultiplied(100, 0, 0, 128);
+    let img = composite(&[
+      (ColorImage::new([1, 1], half), [0, 0], BlendMode::Multiply)]);
+    assert_eq!(img.pixels, vec![half]); // onto transparent keeps the source
+    assert_eq!(composite(&[]).size, [0, 0]);
+    let ex = ColorImage::example().size;
+    let one = ColorImage::new([1, 1], d);
+    assert_eq!(composite(&[(one.clone(), [usize::MAX, 0], BlendMode::Normal)])
+      .size, ex); // offset + size overflows
+    assert_eq!(composite(&[(one, [1 << 40, 1 << 40], BlendMode::Add)]).size,
+      ex); // canvas too large
+  }
+
+  /// nine_slice keeps corners and stretches edges and center
+  #[test]
+  fn test_nine_slice() {
+    let c = |v: u8| Color32::from_rgb(v, 0, 0);
+    let src = ColorImage{size: [3, 3],
+      pixels: (1..=9).map(|v| c(v * 10)).collect()};
+    let img = nine_slice(&src, [1, 1, 1, 1], [5, 4], FilterType::Nearest);
+    assert_eq!(img.size, [5, 4]);
+    assert_eq!(img.pixels[0..5], [c(10), c(20), c(20), c(20), c(30)]);
+    assert_eq!(img.pixels[5..10], [c(40), c(50), c(50), c(50), c(60)]);
+    assert_eq!(img.pixels[10..15], [c(40), c(50), c(50), c(50), c(60)]);
+    assert_eq!(img.pixels[15..20], [c(70), c(80), c(80), c(80), c(90)]);
+    let img = nine_slice(&src, [2, 2, 2, 2], [2, 2], FilterType::Nearest);
+    assert_eq!(img.pixels, vec![c(10), c(20), c(40), c(50)]);
+    let t = Color32::from_rgba_unmultiplied(128, 128, 128, 128);
+    let src = ColorImage::new([4, 4], t);
+    let img = nine_slice(&src, [1, 1, 1, 1], [9, 7], FilterType::Triangle);
+    assert_eq!(img.pixels, vec![t; 63]); // uniform translucent, no seam
+  }
+
+  /// resource_font returns the registered name
+  #[test]
+  fn test_resource_font_name() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let mut fonts = FontDefinitions::empty();
+    let n = bp.resource_font(&mut fonts, "fira", "FiraSans-Regular.ttf",
+      FontFamily::Proportional, true);
+    assert_eq!(n.as_deref(), Some("fira"));
+    assert!(fonts.font_data.contains_key(&n.unwrap()));
+    assert!(bp.resource_font(&mut fonts, "x", "_not_exist_.ttf",
+      FontFamily::Proportional, true).is_none());
+    assert!(!fonts.font_data.contains_key("x"));
+  }
+
+  /// detect_format from the content
+  #[test]
+  fn test_detect_format() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let fmt = |f: &str| bp.detect_format(f, true).unwrap();
+    assert_eq!(fmt("_4c_4x4.png"), ImageFormat::Png);
+    assert_eq!(fmt("_2f_2x2.gif"), ImageFormat::Gif);
+    assert!(matches!(bp.detect_format("FiraSans-Regular.ttf", true),
+      Err(ResourceError::Decode(_))));
+    assert!(matches!(bp.detect_format("_not_exist_.png", true),
+      Err(ResourceError::NotFound(_))));
+  }
+
+  /// histogram of a solid image has a single bin per channel
+  #[test]
+  fn test_histogram() {
+    let img = ColorImage::new([3, 2], Color32::from_rgb(10, 20, 30));
+    let hist = histogram(&img);
+    for (h, v) in hist.iter().zip([10, 20, 30, 255]) {
+      assert_eq!(h[v], 6);
+      assert_eq!(h.iter().filter(|&&n| n > 0).count(), 1);
+    }
+    assert_eq!(mean_color(&img), Color32::from_rgb(10, 20, 30));
+    let img = ColorImage{size: [2, 1],
+      pixels: vec![Color32::BLACK, Color32::WHITE]};
+    assert_eq!(mean_color(&img), Color32::from_rgb(128, 128, 128));
+    assert_eq!(mean_color(&ColorImage::new([0, 0], Color32::RED)),
+      Color32::TRANSPARENT);
+  }
+
+  /// Location maps to p: bool
+  #[test]
+  fn test_location() {
+    assert_eq!(Location::from(true), Location::Relative);
+    assert_eq!(Location::from(false), Location::Absolute);
+    assert!(bool::from(Location::Relative));
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let f = "_4c_4x4.png";
+    assert_eq!(bp.resource_img_at(f, Location::Relative).size, [4, 4]);
+    assert!(bp.read_bytes_at(f, Location::Absolute).is_err());
+    assert!(bp.read_bytes_at("./resources/_4c_4x4.png",
+      Location::Absolute).is_ok());
+    assert!(bp.resource_icon_at(f, Location::Relative).is_some());
+    let mut fonts = FontDefinitions::empty();
+    assert!(bp.resource_font_at(&mut fonts, "fira", "FiraSans-Regular.ttf",
+      FontFamily::Proportional, Location::Relative).is_some());
+  }
+
+  /// brightness +255 saturates rgb to white and keeps alpha
+  #[test]
+  fn test_adjust_gamma() {
+    let src = ColorImage{size: [2, 1], pixels: vec![
+      Color32::from_rgb(10, 100, 200),
+      Color32::from_rgba_unmultiplied(50, 50, 50, 128)]};
+    let img = adjust(&src, 255, 0.0);
+    assert_eq!(img.pixels[0], Color32::WHITE);
+    assert_eq!(img.pixels[1],
+      Color32::from_rgba_unmultiplied(255, 255, 255, 128));
+    let img = adjust(&src, -255, 0.0);
+    assert_eq!(img.pixels[0], Color32::BLACK);
+    let img = gamma_correct(&src, 1.0);
+    assert_eq!(img.pixels, src.pixels);
+    let img = gamma_correct(&src, 2.0);
+    assert_eq!(img.pixels[0], Color32::from_rgb(50, 160, 226));
+    assert_eq!(img.pixels[1],
+      Color32::from_rgba_unmultiplied(113, 113, 113, 128));
+    let gray = ColorImage::new([1, 1],
+      Color32::from_rgba_unmultiplied(128, 128, 128, 128));
+    assert_eq!(gamma_correct(&gray, 2.0).pixels[0].to_srgba_unmultiplied(),
+      [181, 181, 181, 128]); // sqrt(128 / 255) * 255
+  }
+
+  /// identity settings keep translucent pixels byte identical
+  #[test]
+  fn test_adjust_gamma_identity() {
+    let src = ColorImage{size: [2, 1], pixels: vec![
+      Color32::from_rgba_unmultiplied(128, 128, 128, 128),
+      Color32::from_rgba_unmultiplied(200, 100, 0, 64)]};
+    assert_eq!(gamma_correct(&src, 1.0).pixels, src.pixels);
+    assert_eq!(adjust(&src, 0, 0.0).pixels, src.pixels);
+  }
+
+  /// premultiplied resize keeps white edges from darkening
+  #[test]
+  fn test_resized_premultiplied() {
+    let pixels = (0..256).map(|i| {
+      let (x, y) = ((i % 16) as f32 - 7.5, (i / 16) as f32 - 7.5);
+      match x * x + y * y < 36.0 {
+      true => Color32::WHITE,
+      false => Color32::TRANSPARENT
+      }
+    }).collect();
+    let src = ColorImage{size: [16, 16], pixels};
+    for filter in [FilterType::Triangle, FilterType::Lanczos3] {
+      let img = resized_premultiplied(&src, [5, 5], filter);
+      assert_eq!(img.size, [5, 5]);
+      assert!(img.pixels.iter().any(|c| c.a() > 0 && c.a() < 255)); // edge
+      for c in img.pixels {
+        let (r, g, b, a) = c.to_tuple();
+        assert_eq!((r, g, b), (a, a, a)); // white when unpremultiplied
+      }
+    }
+  }
+
+  /// edge extend repeats the border pixels outward
+  #[test]
+  fn test_resize_with_edge_extend() {
+    let px = [Color32::RED, Color32::GREEN, Color32::BLUE, Color32::WHITE];
+    let src = ColorImage{size: [2, 2], pixels: px.to_vec()};
+    let img = resize_with_edge_extend(&src, [2, 2], FilterType::Nearest, 1);
+    assert_eq!(img.size, [4, 4]);
+    assert_eq!(img.pixels[0..4], [px[0], px[0], px[1], px[1]]);
+    assert_eq!(img.pixels[4..8], [px[0], px[0], px[1], px[1]]);
+    assert_eq!(img.pixels[12..16], [px[2], px[2], px[3], px[3]]);
+    let img = resize_with_edge_extend(&src, [4, 4], FilterType::Nearest, 2);
+    assert_eq!(img.size, [8, 8]);
+    let img = resize_with_edge_extend(&src, [2, 2], FilterType::Nearest, 0);
+    assert_eq!(img.pixels, src.pixels);
+    let src = ColorImage::new([2, 2],
+      Color32::from_rgba_unmultiplied(128, 128, 128, 128));
+    let img = resize_with_edge_extend(&src, [2, 2], FilterType::Triangle, 1);
+    assert_eq!(img.pixels, vec![src.pixels[0]; 16]); // translucent
+  }
+
+  /// fonts are registered from zip entries
+  #[cfg(feature = "zip")]
+  #[test]
+  #[allow(deprecated)]
+  fn test_zip_fonts() {
+    let zr = ZipResources::new(PathBuf::from("./resources/_resources.zip"))
+      .unwrap();
+    let f = "fonts/FiraSans-Regular.ttf";
+    let mut fonts = FontDefinitions::empty();
+    assert!(zr.resource_font(&mut fonts, "fira", f,
+      FontFamily::Proportional, true).is_some());
+    assert!(fonts.font_data.contains_key("fira"));
+    let fonts = zr.reg_fonts(vec![("zfira", f, FontFamily::Monospace)]);
+    assert!(fonts.font_data.contains_key("zfira"));
+    assert_eq!(fonts.families[&FontFamily::Monospace][0], "zfira");
+  }
+
+  /// exists without loading
+  #[test]
+  fn test_exists() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    assert!(bp.exists("_4c_4x4.png", true));
+    assert!(!bp.exists("_not_exist_.png", true));
+    assert!(bp.exists("./resources/_4c_4x4.png", false));
+    assert!(!bp.exists("_4c_4x4.png", false));
+    let bp = ResourcesBase::new(PathBuf::from("./_none_"))
+      .with_searchpath(PathBuf::from("./resources"));
+    assert!(bp.exists("_4c_4x4.png", true));
+  }
+
+  /// exists checks the entries of zip
+  #[cfg(feature = "zip")]
+  #[test]
+  fn test_zip_exists() {
+    let zr = ZipResources::new(PathBuf::from("./resources/_resources.zip"))
+      .unwrap();
+    assert!(zr.exists("fonts/FiraSans-Regular.ttf", true));
+    assert!(!zr.exists("FiraSans-Regular.ttf", true));
+    assert!(zr.exists("./resources/_4c_4x4.png", false));
+  }
+
+  /// opacity 0.5 halves alpha and keeps straight rgb
+  #[test]
+  fn test_set_opacity() {
+    let src = ColorImage::new([1, 1], Color32::from_rgb(200, 100, 50));
+    let img = set_opacity(&src, 0.5);
+    assert_eq!(img.pixels[0],
+      Color32::from_rgba_unmultiplied(200, 100, 50, 128));
+    let src = ColorImage::new([1, 1], Color32::from_gray(128));
+    assert_eq!(set_opacity(&src, 0.5).pixels[0],
+      Color32::from_rgba_premultiplied(93, 93, 93, 128));
+    let half = set_opacity(&src, 0.5);
+    assert_eq!(set_opacity(&half, 0.5).pixels[0],
+      Color32::from_rgba_unmultiplied(128, 128, 128, 64)); // translucent
+    assert_eq!(set_opacity(&src, 2.0).pixels, src.pixels);
+    assert_eq!(set_opacity(&src, -1.0).pixels[0], Color32::TRANSPARENT);
+  }
+
+  /// validate size and pixel count
+  #[test]
+  fn test_validate() {
+    assert!(validate(&ColorImage::new([3, 2], Color32::RED)).is_ok());
+    assert!(validate(&ColorImage::new([0, 2], Color32::RED)).is_ok());
+    let bad = ColorImage{size: [3, 2], pixels: vec![Color32::RED; 5]};
+    assert_eq!(validate(&bad).unwrap_err(),
+      "size 3x2 needs 6 pixels but has 5");
+    let bad = ColorImage{size: [usize::MAX, 2], pixels: vec![]};
+    assert!(validate(&bad).is_err());
+  }
+
+  /// solid color image and empty one
+  #[test]
+  fn test_solid_color_image() {
+    let img = solid_color_image([3, 2], Color32::RED);
+    assert_eq!(img.pixels, vec![Color32::RED; 6]);
+    assert!(validate(&img).is_ok());
+    let img = solid_color_image([0, 5], Color32::RED);
+    assert_eq!((img.size, img.pixels.len()), ([0, 5], 0));
+  }
+
+  /// img with the modification time of the file
+  #[test]
+  fn test_resource_img_with_mtime() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let (img, t) = bp.resource_img_with_mtime("_4c_4x4.png", true).unwrap();
+    assert_eq!(img.size, [4, 4]);
+    let m = fs::metadata("./resources/_4c_4x4.png").unwrap();
+    assert_eq!(t, m.modified().unwrap());
+    assert_eq!(bp.modified("_4c_4x4.png", true).unwrap(), t);
+    assert!(matches!(bp.resource_img_with_mtime("_not_exist_.png", true),
+      Err(ResourceError::NotFound(_))));
+  }
+
+  /// zip entries have the modification time of the archive
+  #[cfg(feature = "zip")]
+  #[test]
+  fn test_zip_modified() {
+    let f = "./resources/_resources.zip";
+    let zr = ZipResources::new(PathBuf::from(f)).unwrap();
+    let (img, t) = zr.resource_img_with_mtime("_4c_4x4.png", true).unwrap();
+    assert_eq!(img.size, [4, 4]);
+    assert_eq!(t, fs::metadata(f).unwrap().modified().unwrap());
+    assert!(matches!(zr.modified("_not_exist_.png", true),
+      Err(ResourceError::NotFound(_))));
+  }
+
+  /// split then merge reproduces the original
+  #[test]
+  fn test_split_merge_channels() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let im = bp.resource_img("_4c_4x4.png", true);
+    let [r, g, b, a] = split_channels(&im);
+    assert_eq!(r.len(), 16);
+    assert_eq!((r[0], g[0], b[0], a[0]), (255, 0, 0, 255));
+    let img = merge_channels(im.size, &r, &g, &b, &a);
+    assert_eq!(img.pixels, im.pixels);
+    let img = merge_channels([2, 1], &[1], &[2], &[3], &[4]);
+    assert_eq!(img.pixels[1], Color32::TRANSPARENT);
+  }
+
+  /// run the future on this thread (parked until woken)
+  #[cfg(feature = "web")]
+  fn block_on<F: std::future::Future>(f: F) -> F::Output {
+    struct Unpark(std::thread::Thread);
+    impl std::task::Wake for Unpark {
+      fn wake(self: std::sync::Arc<Self>) { self.0.unpark(); }
+    }
+    let waker = std::sync::Arc::new(Unpark(std::thread::current())).into();
+    let mut cx = std::task::Context::from_waker(&waker);
+    let mut f = std::pin::pin!(f);
+    loop {
+      match f.as_mut().poll(&mut cx) {
+      std::task::Poll::Ready(r) => return r,
+      std::task::Poll::Pending => std::thread::park()
+      }
+    }
+  }
+
+  /// fetch from a local server (no network), 404 and invalid url
+  #[cfg(feature = "web")]
+  #[test]
+  fn test_fetch() {
+    use std::io::Write;
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let png = include_bytes!("../resources/_4c_4x4.png");
+    let server = std::thread::spawn(move || {
+      for _ in 0..2 {
+        let (mut st, _) = listener.accept().unwrap();
+        let mut req = [0u8; 1024];
+        let n = st.read(&mut req).unwrap();
+        let (status, body) = match req[..n].starts_with(b"GET /a.png ") {
+        true => ("200 OK", &png[..]),
+        false => ("404 Not Found", &b""[..])
+        };
+        write!(st, "HTTP/1.1 {}\r\nContent-Length: {}\r\n", status,
+          body.len()).unwrap();
+        st.write_all(b"Connection: close\r\n\r\n").unwrap();
+        st.write_all(body).unwrap();
+      }
+    });
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let img = block_on(bp.fetch_img(&format!("http://{}/a.png", addr)));
+    assert_eq!(img.unwrap().size, [4, 4]);
+    let r = block_on(bp.fetch_bytes(&format!("http://{}/b.png", addr)));
+    assert!(matches!(r, Err(ResourceError::NotFound(_))));
+    server.join().unwrap();
+    assert!(block_on(bp.fetch_bytes("not a url")).is_err());
+  }
+
+  /// embedded assets
+  #[cfg(feature = "rust-embed")]
+  #[derive(rust_embed::RustEmbed)]
+  #[folder = "resources/"]
+  struct Assets;
+
+  /// img and font from embedded assets
+  #[cfg(feature = "rust-embed")]
+  #[test]
+  fn test_embedded_resources() {
+    let er = EmbeddedResources::<Assets>::new();
+    assert_eq!(er.resource_img("_4c_4x4.png", true).size, [4, 4]);
+    assert!(er.exists("FiraSans-Regular.ttf", true));
+    assert!(!er.exists("_not_exist_.png", true));
+    assert!(matches!(er.read_bytes("_not_exist_.png", true),
+      Err(ResourceError::NotFound(_))));
+    let mut fonts = FontDefinitions::empty();
+    assert!(er.resource_font(&mut fonts, "fira", "FiraSans-Regular.ttf",
+      FontFamily::Proportional, true).is_some());
+    assert!(fonts.font_data.contains_key("fira"));
+    assert_eq!(er.list_resources("", &["TTF"]).unwrap(),
+      vec![PathBuf::from("FiraSans-Regular.ttf")]);
+  }
+
+  /// list files recursively by extension (case insensitive)
+  #[test]
+  fn test_list_resources() {
+    let dir = std::env::temp_dir().join("egui_resources_test_list");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("icons/sub")).unwrap();
+    for f in ["a.PNG", "b.txt", "icons/c.png", "icons/sub/d.jpg"] {
+      fs::write(dir.join(f), b"").unwrap();
+    }
+    let bd = ResourcesBase::new(dir.clone());
+    assert_eq!(bd.list_resources("", &["png", ".jpg"]).unwrap(), vec![
+      PathBuf::from("a.PNG"), PathBuf::from("icons/c.png"),
+      PathBuf::from("icons/sub/d.jpg")]);
+    assert_eq!(bd.list_resources("icons", &[]).unwrap().len(), 2);
+    assert!(matches!(bd.list_resources("_x_", &[]),
+      Err(ResourceError::NotFound(_))));
+    let sp = std::env::temp_dir().join("egui_resources_test_list_sp");
+    let _ = fs::remove_dir_all(&sp);
+    fs::create_dir_all(sp.join("icons")).unwrap();
+    for f in ["a.PNG", "icons/e.png"] { fs::write(sp.join(f), b"").unwrap(); }
+    let bs = ResourcesBase::new(dir.clone()).with_searchpath(sp);
+    assert_eq!(bs.list_resources("icons", &["png"]).unwrap(), vec![
+      PathBuf::from("icons/c.png"), PathBuf::from("icons/e.png")]);
+    assert_eq!(bs.list_resources("", &["png"]).unwrap().len(), 3); // deduped
+    #[cfg(unix)]
+    {
+      std::os::unix::fs::symlink(&dir, dir.join("icons/loop")).unwrap();
+      assert_eq!(bd.list_resources("", &["png", ".jpg"]).unwrap().len(), 3);
+    }
+  }
+
+  /// dpi scale with fractional factors
+  #[test]
+  fn test_resized_for_dpi() {
+    let src = solid_color_image([4, 4], Color32::RED);
+    let f = FilterType::Nearest;
+    assert_eq!(resized_for_dpi(&src, [16, 8], 1.0, f).size, [16, 8]);
+    assert_eq!(resized_for_dpi(&src, [16, 8], 1.25, f).size, [20, 10]);
+    assert_eq!(resized_for_dpi(&src, [15, 9], 1.5, f).size, [23, 14]);
+    assert_eq!(resized_for_dpi(&src, [16, 8], 2.0, f).size, [32, 16]);
+    let src = solid_color_image([4, 4],
+      Color32::from_rgba_unmultiplied(128, 128, 128, 128));
+    let img = resized_for_dpi(&src, [4, 4], 1.0, FilterType::Triangle);
+    assert_eq!(img.pixels, src.pixels); // translucent
+  }
+
+  /// trim centered 2x2 in 4x4
+  #[test]
+  fn test_trim_transparent() {
+    let mut src = solid_color_image([4, 4], Color32::TRANSPARENT);
+    for i in [5, 6, 9, 10] { src.pixels[i] = Color32::RED; }
+    let (img, offs) = trim_transparent(&src);
+    assert_eq!((img.size, offs), ([2, 2], [1, 1, 1, 1]));
+    assert_eq!(img.pixels, vec![Color32::RED; 4]);
+    src.pixels[3] = Color32::BLUE; // top right
+    let (img, offs) = trim_transparent(&src);
+    assert_eq!((img.size, offs), ([3, 3], [0, 0, 1, 1]));
+    let src = solid_color_image([3, 2], Color32::TRANSPARENT);
+    let (img, offs) = trim_transparent(&src);
+    assert_eq!((img.size, offs), ([0, 0], [2, 0, 0, 3]));
+    let bad = ColorImage{size: [3, 3], pixels: vec![]}; // no panic
+    let (img, offs) = trim_transparent(&bad);
+    assert_eq!((img.size, offs), ([0, 0], [3, 0, 0, 3]));
+  }
+
+  /// tweak is set on the FontData
+  #[test]
+  fn test_resource_font_tweak() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let mut fonts = FontDefinitions::empty();
+    let tweak = FontTweak{scale: 0.8, y_offset_factor: -0.1,
+      ..Default::default()};
+    bp.resource_font_tweak(&mut fonts, "icons", "FiraSans-Regular.ttf",
+      FontFamily::Proportional, tweak, true);
+    let fd = &fonts.font_data["icons"];
+    assert_eq!((fd.tweak.scale, fd.tweak.y_offset_factor), (0.8, -0.1));
+    assert_eq!(fonts.families[&FontFamily::Proportional], vec!["icons"]);
+  }
+
+  /// bounded cache evicts the least recently used
+  #[test]
+  fn test_texture_cache_capacity() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let mut tc = TextureCache::with_capacity(bp, 80);
+    let (a, b, c) = ("_4c_4x4.png", "_2f_2x2.gif", "./resources/_2f_2x2.gif");
+    assert_eq!(tc.cached_img(a, true).size, [4, 4]); // 64 bytes
+    assert_eq!(tc.cached_img(b, true).size, [2, 2]); // 16 bytes
+    assert_eq!(tc.bytes(), 80);
+    tc.cached_img(a, true); // a is newer than b
+    tc.cached_img(c, false); // over 80 then b is evicted
+    assert!(tc.imgs.contains_key(a));
+    assert!(!tc.imgs.contains_key(b));
+    assert!(tc.imgs.contains_key(c));
+    assert_eq!(tc.bytes(), 80);
+    tc.invalidate(a); // running total follows removal
+    assert_eq!(tc.bytes(), 16);
+    tc.clear();
+    assert_eq!(tc.bytes(), 0);
+  }
+
+  /// fonts are installed into Context
+  #[test]
+  fn test_install_fonts() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let ctx = Context::default();
+    assert!(matches!(bp.install_fonts(&ctx, vec![
+      ("x", "_not_exist_.ttf", FontFamily::Proportional, true)]),
+      Err(ResourceError::NotFound(_))));
+    bp.install_fonts(&ctx, vec![
+      ("fira", "FiraSans-Regular.ttf", FontFamily::Proportional, true),
+      ("mono", "./resources/FiraSans-Regular.ttf", FontFamily::Monospace,
+        false)]).unwrap();
+    let _ = ctx.run(Default::default(), |_| {}); // fonts are set at the frame
+    assert!(ctx.fonts(|f| ["fira", "mono"].iter().all(|n|
+      f.lock().fonts.definitions().font_data.contains_key(*n))));
+  }
+
+  /// magenta becomes transparent
+  #[test]
+  fn test_color_key() {
+    let m = Color32::from_rgb(255, 0, 255);
+    let src = ColorImage{size: [3, 1], pixels: vec![
+      m, Color32::from_rgb(250, 4, 251), Color32::from_rgb(10, 20, 30)]};
+    let img = color_key(&src, m, 0);
+    assert_eq!(img.pixels[0], Color32::TRANSPARENT);
+    assert_eq!(img.pixels[1..], src.pixels[1..]);
+    let img = color_key(&src, m, 5);
+    assert_eq!(img.pixels[1], Color32::TRANSPARENT);
+    assert_eq!(img.pixels[2], src.pixels[2]);
+  }
+
+  /// compact type for gray, opaque and translucent
+  #[test]
+  fn test_dynamic_image_compact() {
+    let img = dynamic_image_compact(&solid_color_image([2, 2],
+      Color32::from_gray(100)));
+    assert!(matches!(img, DynamicImage::ImageLuma8(_)));
+    assert_eq!(img.to_rgba8().get_pixel(1, 1).0, [100, 100, 100, 255]);
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let im = bp.resource_img("_4c_4x4.png", true);
+    let img = dynamic_image_compact(&im);
+    assert!(matches!(img, DynamicImage::ImageRgb8(_)));
+    assert_eq!(color_image_from_dynamic_image(img).pixels, im.pixels);
+    let img = dynamic_image_compact(&solid_color_image([2, 2],
+      Color32::from_rgba_premultiplied(10, 10, 10, 128)));
+    assert!(matches!(img, DynamicImage::ImageRgba8(_)));
+    let img = dynamic_image_compact(&ColorImage::new([0, 0], Color32::RED));
+    assert_eq!((img.width(), img.height()), (0, 0));
+  }
+
+  /// png rows (or adam7 passes) are called back progressively, others once
+  #[test]
+  fn test_decode_progressive() {
+    let b = include_bytes!("../resources/_4c_4x4.png");
+    let mut partials = vec![];
+    let img = decode_progressive(b, |im| partials.push(im.pixels.clone()))
+      .unwrap();
+    assert_eq!(img.pixels, color_image_from_memory(b).unwrap().pixels);
+    assert_eq!(partials.len(), 4);
+    assert_eq!(partials[0][0], Color32::RED);
+    assert_eq!(partials[0][4], Color32::TRANSPARENT);
+    assert_eq!(partials[3], img.pixels);
+    let b = include_bytes!("../resources/_adam7_8x8.png");
+    partials.clear();
+    let img = decode_progressive(b, |im| partials.push(im.pixels.clone()))
+      .unwrap();
+    assert_eq!(img.pixels, color_image_from_memory(b).unwrap().pixels);
+    assert_eq!(partials.len(), 7);
+    assert_eq!(partials[0][0], Color32::from_rgb(0, 0, 128));
+    assert_eq!(partials[0][4], Color32::TRANSPARENT);
+    assert_eq!(partials[1][4], Color32::from_rgb(128, 0, 128));
+    assert_eq!(partials[6], img.pixels);
+    let b = include_bytes!("../resources/_2f_2x2.gif");
+    let mut n = 0;
+    assert_eq!(decode_progressive(b, |_| n += 1).unwrap().size, [2, 2]);
+    assert_eq!(n, 1);
+    assert!(decode_progressive(&b[..8], |_| ()).is_err());
+  }
+
+  /// mostly red with some blue is red
+  #[test]
+  fn test_dominant_color() {
+    let mut src = solid_color_image([4, 4], Color32::from_rgb(250, 0, 0));
+    for i in 0..5 { src.pixels[i] = Color32::BLUE; }
+    src.pixels[5] = Color32::from_rgb(240, 10, 0);
+    for i in 6..9 { src.pixels[i] = Color32::TRANSPARENT; }
+    assert!(mean_color(&src).b() > 0); // mean is washed out by blue
+    assert_eq!(dominant_color(&src, 4), Color32::from_rgb(249, 1, 0));
+    assert_eq!(dominant_color(&src, 256), Color32::from_rgb(250, 0, 0));
+    let src = solid_color_image([2, 2], Color32::TRANSPARENT);
+    assert_eq!(dominant_color(&src, 8), Color32::TRANSPARENT);
+  }
+
+  /// try_reg_fonts detects duplicate names
+  #[test]
+  fn test_try_reg_fonts() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let f = "FiraSans-Regular.ttf";
+    let fonts = bp.try_reg_fonts(vec![
+      ("a", f, FontFamily::Proportional, true),
+      ("b", f, FontFamily::Monospace, true)]).unwrap();
+    assert!(fonts.font_data.contains_key("a"));
+    assert!(fonts.font_data.contains_key("b"));
+    let e = bp.try_reg_fonts(vec![
+      ("a", f, FontFamily::Proportional, true),
+      ("a", f, FontFamily::Monospace, true)]).unwrap_err();
+    assert_eq!(e.to_string(),
+      "invalid: duplicate font name: a (FiraSans-Regular.ttf)");
+    assert!(bp.try_reg_fonts(vec![
+      ("Hack", f, FontFamily::Monospace, true)]).is_err()); // default font
+    assert!(matches!(bp.try_reg_fonts(vec![
+      ("c", "_not_exist_.ttf", FontFamily::Monospace, true)]),
+      Err(ResourceError::NotFound(_))));
+  }
+
+  /// load and resize at once is same as the two steps
+  #[test]
+  fn test_resource_img_resized() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let f = "_4c_4x4.png";
+    let img = bp.resource_img_resized(f, true, [2, 2], FilterType::Nearest);
+    let two = resized_copy_from([2, 2], &bp.resource_img(f, true),
+      FilterType::Nearest);
+    assert_eq!(img.pixels, two.pixels);
+    let img = bp.resource_img_resized("_not_exist_.png", true, [2, 2],
+      FilterType::Nearest);
+    assert_eq!(img.size, ColorImage::example().size);
+  }
+
+  /// options set once on ResourcesBase are used by *_with_options
+  #[test]
+  fn test_resource_options() {
+    let f = "_4c_4x4.png";
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    assert_eq!(bp.options, ResourceOptions::default());
+    let img = bp.resource_img_with_options(f, true);
+    assert_eq!(img.pixels, bp.resource_img(f, true).pixels);
+    let o = ResourceOptions{filter: FilterType::Nearest,
+      alpha: AlphaMode::Straight, upscale: false};
+    let bp = bp.with_options(o);
+    let img = bp.resource_img_resized_with_options(f, true, [2, 2]);
+    let two = bp.resource_img_resized(f, true, [2, 2], FilterType::Nearest);
+    assert_eq!(img.pixels, two.pixels);
+    let img = bp.resource_img_resized_with_options(f, true, [16, 8]);
+    assert_eq!(img.size, [4, 2]); // not upscaled, aspect kept
+    let bp = bp.with_options(ResourceOptions{upscale: true, ..o});
+    let img = bp.resource_img_resized_with_options(f, true, [16, 8]);
+    assert_eq!(img.size, [16, 8]);
+  }
+
+  /// mask is alpha, or luma without alpha
+  #[test]
+  fn test_resource_mask() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let (mask, wh) = bp.resource_mask("_4c_4x4.png", true).unwrap(); // rgb
+    assert_eq!((mask.len(), wh), (16, [4, 4]));
+    assert_eq!(mask[0], 54); // luma of red
+    let dir = std::env::temp_dir().join("egui_resources_test_mask");
+    fs::create_dir_all(&dir).unwrap();
+    let mut im = solid_color_image([3, 2], Color32::TRANSPARENT);
+    im.pixels[4] = Color32::RED;
+    save_color_image(&im, &dir.join("m.png"), ImageFormat::Png).unwrap();
+    let (mask, wh) = ResourcesBase::new(dir).resource_mask("m.png", true)
+      .unwrap();
+    assert_eq!(wh, [3, 2]);
+    assert_eq!(mask, vec![0, 0, 0, 0, 255, 0]);
+  }
+
+  /// AlphaMode selects straight or premultiplied conversion
+  #[test]
+  fn test_color_image_from_dynamic_image_with() {
+    let im = || DynamicImage::from(
+      RgbaImage::from_raw(2, 1, vec![255, 255, 255, 128, 10, 20, 30, 255])
+        .unwrap());
+    let s = color_image_from_dynamic_image_with(im(), AlphaMode::Straight);
+    assert_eq!(s.pixels[0],
+      Color32::from_rgba_unmultiplied(255, 255, 255, 128));
+    assert_eq!(s.pixels, color_image_from_dynamic_image(im()).pixels);
+    let m = color_image_from_dynamic_image_with(im(), AlphaMode::Premultiplied);
+    assert_eq!(m.pixels[0],
+      Color32::from_rgba_premultiplied(255, 255, 255, 128));
+    assert_eq!(m.pixels,
+      color_image_from_dynamic_image_premultiplied(im()).pixels);
+    assert_eq!(s.pixels[1], m.pixels[1]); // opaque is the same
+  }
+
+  /// @Nx assets are preferred then fall back to the base name
+  #[test]
+  fn test_resource_img_scaled() {
+    assert_eq!(scaled_name("icons/icon.png", 2), "icons/icon@2x.png");
+    assert_eq!(scaled_name("./a.b/icon", 3), "./a.b/icon@3x");
+    assert_eq!(scaled_name(".hidden", 2), ".hidden@2x");
+    let dir = std::env::temp_dir().join("egui_resources_test_scaled");
+    fs::create_dir_all(&dir).unwrap();
+    let im = solid_color_image([2, 2], Color32::RED);
+    save_color_image(&im, &dir.join("icon@2x.png"), ImageFormat::Png).unwrap();
+    let im = solid_color_image([1, 1], Color32::BLUE);
+    save_color_image(&im, &dir.join("icon.png"), ImageFormat::Png).unwrap();
+    let bd = ResourcesBase::new(dir);
+    assert_eq!(bd.resource_img_scaled("icon.png", 1, true).size, [1, 1]);
+    assert_eq!(bd.resource_img_scaled("icon.png", 2, true).size, [2, 2]);
+    assert_eq!(bd.resource_img_scaled("icon.png", 3, true).size, [2, 2]);
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    assert_eq!(bp.resource_img_scaled("_4c_4x4.png", 2, true).size, [4, 4]);
+  }
+
+  /// dithered gradient keeps intermediate tones on average
+  #[test]
+  fn test_dither_color_image() {
+    let (w, h) = (32, 8);
+    let src = ColorImage{size: [w, h], pixels: (0..w * h).map(|i|
+      Color32::from_gray((i % w * 255 / (w - 1)) as u8)).collect()};
+    let img = dither_color_image(&src, 2);
+    assert!(img.pixels.iter().all(|c| c.r() == 0 || c.r() == 255));
+    let cols = (0..w).map(|x|
+      (0..h).map(|y| img.pixels[y * w + x].r() as usize).sum::<usize>() / h
+    ).collect::<std::collections::BTreeSet<_>>();
+    assert!(cols.len() > 2);
+    let mid = dither_color_image(&solid_color_image([8, 8],
+      Color32::from_gray(128)), 2);
+    let m = mean_color(&mid).r();
+    assert!((120..=136).contains(&m));
+  }
+
+  /// bounds checked pixel access
+  #[test]
+  fn test_pixel_at() {
+    let mut img = solid_color_image([3, 2], Color32::RED);
+    assert_eq!(pixel_at(&img, 2, 1), Some(Color32::RED));
+    assert_eq!(pixel_at(&img, 3, 0), None);
+    assert_eq!(pixel_at(&img, 0, 2), None);
+    assert!(set_pixel(&mut img, 1, 1, Color32::BLUE));
+    assert_eq!(img.pixels[4], Color32::BLUE);
+    assert!(!set_pixel(&mut img, 3, 1, Color32::BLUE));
+    let mut bad = ColorImage{size: [3, 2], pixels: vec![Color32::RED; 2]};
+    assert_eq!(pixel_at(&bad, 2, 1), None);
+    assert!(!set_pixel(&mut bad, 2, 1, Color32::BLUE));
+  }
+
+  /// merged fonts keep both definitions
+  #[test]
+  fn test_merge_fonts() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let f = "FiraSans-Regular.ttf";
+    let mut base = bp.reg_fonts_into(FontDefinitions::empty(),
+      vec![("a", f, FontFamily::Proportional, true)]);
+    let extra = bp.reg_fonts_into(FontDefinitions::empty(), vec![
+      ("b", f, FontFamily::Proportional, true),
+      ("a", f, FontFamily::Monospace, true)]);
+    merge_fonts(&mut base, extra);
+    assert!(base.font_data.contains_key("a"));
+    assert!(base.font_data.contains_key("b"));
+    assert_eq!(base.families[&FontFamily::Proportional], vec!["a", "b"]);
+    assert_eq!(base.families[&FontFamily::Monospace], vec!["a"]);
+    let layered = bp.reg_fonts_into(base,
+      vec![("c", f, FontFamily::Proportional, true)]);
+    assert_eq!(layered.families[&FontFamily::Proportional],
+      vec!["c", "a", "b"]);
+    let mut fonts = FontDefinitions::default();
+    merge_fonts(&mut fonts, layered);
+    assert!(fonts.font_data.contains_key("Hack"));
+    assert!(fonts.font_data.contains_key("c"));
+  }
+
+  /// every EXIF orientation maps to flip and rotate
+  #[test]
+  fn test_oriented_color_image() {
+    let px = [Color32::RED, Color32::GREEN, Color32::BLUE, Color32::WHITE,
+      Color32::BLACK, Color32::YELLOW];
+    let src = ColorImage{size: [3, 2], pixels: px.to_vec()}; // r g b / w k y
+    let o = |n| oriented_color_image(&src, n);
+    assert_eq!(o(1).pixels, src.pixels);
+    assert_eq!(o(9).pixels, src.pixels);
+    assert_eq!(o(2).pixels, flip_horizontal(&src).pixels);
+    assert_eq!(o(3).pixels, rotate180(&src).pixels);
+    assert_eq!(o(4).pixels, flip_vertical(&src).pixels);
+    let t = o(5); // transpose (x, y) to (y, x)
+    assert_eq!(t.size, [2, 3]);
+    assert_eq!(t.pixels, vec![px[0], px[3], px[1], px[4], px[2], px[5]]);
+    assert_eq!(o(6).pixels, rotate90(&src).pixels);
+    let t = o(7); // transverse
+    assert_eq!(t.pixels, vec![px[5], px[2], px[4], px[1], px[3], px[0]]);
+    assert_eq!(o(8).pixels, rotate270(&src).pixels);
+  }
+
+  /// EXIF orientation 6 is rotated 90 degrees clockwise
+  #[cfg(feature = "exif")]
+  #[test]
+  fn test_resource_img_oriented() {
+    let bp = ResourcesBase::new(PathBuf::from("./resources"));
+    let img = bp.resource_img_oriented("_exif6_16x8.jpg", true).unwrap();
+    assert_eq!(img.size, [8, 16]); // left red half goes to the top
+    let near = |c: Color32, e: Color32| compare_color_images(
+      &solid_color_image([1, 1], c), &solid_color_image([1, 1], e), 16);
+    assert!(near(pixel_at(&img, 4, 2).unwrap(), Color32::RED));
+    assert!(near(pixel_at(&img, 4, 13).unwrap(), Color32::BLUE));
+    let img = bp.resource_img_oriented("_4c_4x4.png", true).unwrap();
+    assert_eq!(img.pixels, bp.resource_img("_4c_4x4.png", true).pixels);
+  }
+}